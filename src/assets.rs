@@ -1,18 +1,33 @@
-use bevy::asset::Handle;
+use bevy::asset::{AssetPath, RecursiveDependencyLoadState, UntypedHandle};
 use bevy::prelude::*;
+use bevy::utils::HashSet;
+use bevy_egui::{EguiContexts, egui};
 
-use crate::GameState;
-use crate::ui::{MONOSPACE_FONT, PROPORTIONAL_FONT};
+use crate::{GameState, MySystemSets};
+
+pub const PROPORTIONAL_FONT_PATH: &str = "font/fira_go/FiraGO-Regular.ttf";
+pub const MONOSPACE_FONT_PATH: &str = "font/fira_mono/FiraMono-Regular.ttf";
 
 pub struct LoadingPlugin;
 
 impl Plugin for LoadingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::LoadingAssets), load_assets)
+        app.init_resource::<Preload>()
+            .add_systems(OnEnter(GameState::LoadingAssets), load_assets)
+            .add_systems(
+                Update,
+                (update_assets, loading_screen)
+                    .chain()
+                    .run_if(in_state(GameState::LoadingAssets))
+                    .in_set(MySystemSets::Egui),
+            )
             .add_systems(
                 Update,
-                update_assets.run_if(in_state(GameState::LoadingAssets)),
-            );
+                loading_failed_screen
+                    .run_if(in_state(GameState::LoadingFailed))
+                    .in_set(MySystemSets::Egui),
+            )
+            .add_systems(OnExit(GameState::LoadingFailed), remove_loading_error);
     }
 }
 
@@ -38,40 +53,208 @@ impl GameAssets {
     pub fn monospace_font(&self) -> Handle<Font> {
         self.monospace_font.to_owned()
     }
+}
 
-    fn has_finished_preloading(&self, _server: &AssetServer) -> bool {
-        // TODO: make this more elegant
-        /*match server.get_group_load_state(assets.get_handles()) {
-            LoadState::Failed | LoadState::NotLoaded => {
-                panic!("error loading assets")
-            }
-            LoadState::Loaded => state.set(GameState::ConnectMenu),
-            _ => {}
-        };*/
+/// Handles that have to be loaded before leaving [`GameState::LoadingAssets`].
+///
+/// Plugins add theirs when entering the state.
+#[derive(Resource, Debug, Default)]
+pub struct Preload {
+    handles: HashSet<UntypedHandle>,
+}
 
-        true
+impl Preload {
+    pub fn add<A: Asset>(&mut self, handle: &Handle<A>) {
+        self.handles.insert(handle.clone().untyped());
+    }
+
+    fn status(&self, server: &AssetServer) -> PreloadStatus {
+        PreloadStatus::of(self.handles.iter().map(|handle| {
+            (
+                handle.path(),
+                server.recursive_dependency_load_state(handle.id()),
+            )
+        }))
     }
 }
 
-fn load_assets(
-    mut commands: Commands,
-    mut fonts: ResMut<Assets<Font>>,
-    /*server: Res<AssetServer>,*/
-) {
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PreloadStatus {
+    Loading {
+        loaded: usize,
+        total: usize,
+    },
+    Finished,
+    /// Paths and errors of the assets that could not be loaded.
+    Failed(Vec<String>),
+}
+
+impl PreloadStatus {
+    fn of<'a>(
+        states: impl Iterator<Item = (Option<&'a AssetPath<'static>>, RecursiveDependencyLoadState)>,
+    ) -> Self {
+        let mut loaded = 0;
+        let mut total = 0;
+        let mut errors = Vec::new();
+        for (path, state) in states {
+            total += 1;
+            match state {
+                RecursiveDependencyLoadState::Loaded => loaded += 1,
+                RecursiveDependencyLoadState::Failed(e) => match path {
+                    Some(path) => errors.push(format!("{path}: {e}")),
+                    None => errors.push(e.to_string()),
+                },
+                _ => {}
+            }
+        }
+        if !errors.is_empty() {
+            errors.sort();
+            PreloadStatus::Failed(errors)
+        } else if loaded == total {
+            PreloadStatus::Finished
+        } else {
+            PreloadStatus::Loading { loaded, total }
+        }
+    }
+}
+
+/// Why preloading failed, shown until the user retries.
+#[derive(Resource, Debug)]
+struct LoadingError(Vec<String>);
+
+fn load_assets(mut commands: Commands, server: Res<AssetServer>, mut preload: ResMut<Preload>) {
     info!("pre-loading assets...");
-    commands.insert_resource(GameAssets::new(
-        fonts.add(Font::try_from_bytes(Vec::from(PROPORTIONAL_FONT)).unwrap()),
-        fonts.add(Font::try_from_bytes(Vec::from(MONOSPACE_FONT)).unwrap()),
-    ));
+    let assets = GameAssets::new(
+        server.load(PROPORTIONAL_FONT_PATH),
+        server.load(MONOSPACE_FONT_PATH),
+    );
+    preload.add(&assets.font);
+    preload.add(&assets.monospace_font);
+    commands.insert_resource(assets);
 }
 
 fn update_assets(
+    mut commands: Commands,
     mut state: ResMut<NextState<GameState>>,
     server: Res<AssetServer>,
-    assets: Res<GameAssets>,
+    preload: Res<Preload>,
 ) {
-    if assets.has_finished_preloading(&server) {
-        info!("...finished pre-loading assets");
-        state.set(GameState::ConnectMenu);
+    match preload.status(&server) {
+        PreloadStatus::Finished => {
+            info!("...finished pre-loading assets");
+            state.set(GameState::ConnectMenu);
+        }
+        PreloadStatus::Failed(errors) => {
+            error!("pre-loading assets failed: {errors:?}");
+            commands.insert_resource(LoadingError(errors));
+            state.set(GameState::LoadingFailed);
+        }
+        PreloadStatus::Loading { .. } => {}
+    }
+}
+
+fn loading_screen(mut egui_context: EguiContexts, server: Res<AssetServer>, preload: Res<Preload>) {
+    let (loaded, total) = match preload.status(&server) {
+        PreloadStatus::Loading { loaded, total } => (loaded, total),
+        _ => return,
+    };
+    egui::CentralPanel::default().show(egui_context.ctx_mut(), |ui| {
+        ui.vertical_centered(|ui| {
+            ui.heading("Loading");
+            ui.add(egui::ProgressBar::new(loaded as f32 / total as f32).show_percentage());
+            ui.label(format!("{loaded} of {total} assets"));
+        });
+    });
+}
+
+fn loading_failed_screen(
+    mut egui_context: EguiContexts,
+    mut state: ResMut<NextState<GameState>>,
+    server: Res<AssetServer>,
+    preload: Res<Preload>,
+    error: Res<LoadingError>,
+) {
+    egui::CentralPanel::default().show(egui_context.ctx_mut(), |ui| {
+        ui.vertical_centered(|ui| {
+            ui.heading("Loading failed");
+            ui.label("Some assets could not be loaded:");
+            for message in &error.0 {
+                ui.colored_label(ui.visuals().error_fg_color, message);
+            }
+            if ui.button("Retry").clicked() {
+                for handle in &preload.handles {
+                    let failed = matches!(
+                        server.recursive_dependency_load_state(handle.id()),
+                        RecursiveDependencyLoadState::Failed(_)
+                    );
+                    if let Some(path) = handle.path().filter(|_| failed) {
+                        server.reload(path.clone_owned());
+                    }
+                }
+                state.set(GameState::LoadingAssets);
+            }
+        });
+    });
+}
+
+fn remove_loading_error(mut commands: Commands) {
+    commands.remove_resource::<LoadingError>();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bevy::asset::AssetLoadError;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_preload_status_loading() {
+        let states = [
+            RecursiveDependencyLoadState::Loaded,
+            RecursiveDependencyLoadState::Loading,
+            RecursiveDependencyLoadState::NotLoaded,
+        ];
+        assert_eq!(
+            PreloadStatus::Loading {
+                loaded: 1,
+                total: 3
+            },
+            PreloadStatus::of(states.into_iter().map(|state| (None, state)))
+        );
+    }
+
+    #[test]
+    fn test_preload_status_finished() {
+        let states = vec![RecursiveDependencyLoadState::Loaded; 2];
+        assert_eq!(
+            PreloadStatus::Finished,
+            PreloadStatus::of(states.into_iter().map(|state| (None, state)))
+        );
+    }
+
+    #[test]
+    fn test_preload_status_failed() {
+        let path = AssetPath::from("font/missing.ttf");
+        let error = Arc::new(AssetLoadError::AssetLoaderPanic {
+            path: path.clone(),
+            loader_name: "font",
+        });
+        let status = PreloadStatus::of(
+            [
+                (None, RecursiveDependencyLoadState::Loading),
+                (
+                    Some(&path),
+                    RecursiveDependencyLoadState::Failed(error.clone()),
+                ),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(
+            PreloadStatus::Failed(vec![format!("font/missing.ttf: {error}")]),
+            status
+        );
     }
 }
@@ -0,0 +1,220 @@
+use std::fmt::{Display, Formatter};
+
+use crate::card::{Card, CardContainer, DeckSpec, UnorderedCards};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DeckError {
+    NotEnoughCards { needed: usize, left: usize },
+}
+
+impl Display for DeckError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeckError::NotEnoughCards { needed, left } => {
+                write!(f, "cannot deal {needed} cards, only {left} are left")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DeckError {}
+
+/// SplitMix64, small and fully specified so a seed gives the same deal on every platform.
+#[derive(Debug, Clone)]
+struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Number in `0..bound`, by multiplying instead of the modulo to keep the bias negligible.
+    fn below(&mut self, bound: usize) -> usize {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+}
+
+/// Cards that have not been dealt yet, the top of the deck is the end of the list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deck {
+    cards: Vec<Card>,
+}
+
+impl Deck {
+    /// All 52 cards in the order of their index.
+    pub fn new() -> Self {
+        Self::from_spec(&DeckSpec::STANDARD)
+    }
+
+    /// The cards of `spec` in the order of their index, without jokers as hands can not hold them.
+    pub fn from_spec(spec: &DeckSpec) -> Self {
+        Self {
+            cards: Card::all_in(spec).collect(),
+        }
+    }
+
+    /// A full deck shuffled with `seed`.
+    pub fn shuffled(seed: u64) -> Self {
+        let mut deck = Self::new();
+        deck.shuffle(seed);
+        deck
+    }
+
+    /// Fisher-Yates shuffle of the remaining cards.
+    pub fn shuffle(&mut self, seed: u64) {
+        let mut rng = SeededRng::new(seed);
+        for i in (1..self.cards.len()).rev() {
+            let j = rng.below(i + 1);
+            self.cards.swap(i, j);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Takes the top card, e.g. to turn up the trump.
+    pub fn draw(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    /// Deals one card at a time to each player in turn, the rest stays in the deck.
+    pub fn deal(
+        &mut self,
+        players: usize,
+        cards_each: usize,
+    ) -> Result<Vec<UnorderedCards>, DeckError> {
+        let needed = players * cards_each;
+        if needed > self.cards.len() {
+            return Err(DeckError::NotEnoughCards {
+                needed,
+                left: self.cards.len(),
+            });
+        }
+        let mut hands = vec![UnorderedCards::new(); players];
+        for i in 0..needed {
+            if let Some(card) = self.cards.pop() {
+                hands[i % players].add(card);
+            }
+        }
+        Ok(hands)
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn hands(deck: &mut Deck, players: usize, cards_each: usize) -> Vec<String> {
+        deck.deal(players, cards_each)
+            .unwrap()
+            .iter()
+            .map(UnorderedCards::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_rng_is_stable() {
+        let mut rng = SeededRng::new(1234567);
+        let values: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+        assert_eq!(
+            vec![
+                6457827717110365317,
+                3203168211198807973,
+                9817491932198370423
+            ],
+            values
+        );
+    }
+
+    #[test]
+    fn test_shuffle_is_a_permutation() {
+        let deck = Deck::shuffled(7);
+        assert_ne!(Deck::new(), deck);
+        let cards: UnorderedCards = deck.cards.iter().copied().collect();
+        assert_eq!(UnorderedCards::all(), cards);
+    }
+
+    #[test]
+    fn test_same_seed_same_deal() {
+        assert_eq!(Deck::shuffled(42), Deck::shuffled(42));
+        assert_ne!(Deck::shuffled(42), Deck::shuffled(43));
+    }
+
+    #[test]
+    fn test_deal_known_seeds() {
+        assert_eq!(
+            vec![
+                "S:KJ87 H:8 D:K962 C:KQ72",
+                "S:A932 H:AKQ D:JT74 C:T5",
+                "S:Q654 H:J62 D:A53 C:983",
+                "S:T H:T97543 D:Q8 C:AJ64",
+            ],
+            hands(&mut Deck::shuffled(0), 4, 13)
+        );
+        assert_eq!(
+            vec!["S:2 H:8 D:3 C:98", "S:- H:2 D:A7 C:76"],
+            hands(&mut Deck::shuffled(2024), 2, 5)
+        );
+    }
+
+    #[test]
+    fn test_deal_keeps_rest() {
+        let mut deck = Deck::new();
+        assert_eq!(
+            vec!["S:AQT H:- D:- C:-", "S:KJ9 H:- D:- C:-"],
+            hands(&mut deck, 2, 3)
+        );
+        assert_eq!(46, deck.len());
+        assert_eq!(Some("8S".parse().unwrap()), deck.draw());
+    }
+
+    #[test]
+    fn test_deal_piquet() {
+        let mut deck = Deck::from_spec(&DeckSpec::PIQUET);
+        deck.shuffle(5);
+        let hands = deck.deal(4, 8).unwrap();
+        assert!(deck.is_empty());
+        assert!(
+            hands
+                .iter()
+                .flatten()
+                .all(|card| DeckSpec::PIQUET.contains(card))
+        );
+    }
+
+    #[test]
+    fn test_deal_not_enough_cards() {
+        let mut deck = Deck::shuffled(1);
+        assert_eq!(
+            Err(DeckError::NotEnoughCards {
+                needed: 56,
+                left: 52
+            }),
+            deck.deal(4, 14)
+        );
+        assert_eq!(52, deck.len());
+    }
+}
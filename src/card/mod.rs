@@ -0,0 +1,1039 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::iter::Map;
+use std::ops::Range;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub use deck::*;
+pub use pbn::*;
+pub use rules::*;
+pub use scoring::*;
+pub use set::*;
+pub use spec::*;
+pub use variant::*;
+
+mod deck;
+mod notation;
+mod pbn;
+mod rules;
+mod scoring;
+mod set;
+mod spec;
+mod variant;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SuitError {
+    InvalidName(String),
+    InvalidIndex(u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RankError {
+    InvalidName(String),
+    InvalidIndex(u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CardError {
+    Suit(SuitError),
+    Rank(RankError),
+    /// Index past the last card of [`DeckSpec::STANDARD`].
+    InvalidIndex(u8),
+    /// Text that is not a card or hand in short notation.
+    InvalidNotation(String),
+}
+
+impl From<SuitError> for CardError {
+    fn from(error: SuitError) -> Self {
+        Self::Suit(error)
+    }
+}
+
+impl From<RankError> for CardError {
+    fn from(error: RankError) -> Self {
+        Self::Rank(error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CardVariant {
+    Back,
+    Front(Card),
+}
+
+impl Display for CardVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardVariant::Back => write!(f, "Back"),
+            CardVariant::Front(card) => write!(f, "{card}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Card {
+    pub suit: Suit,
+    pub rank: Rank,
+}
+
+impl CardEnum for Card {
+    const COUNT: u8 = Suit::COUNT * Rank::COUNT;
+}
+
+impl TryFrom<u8> for Card {
+    type Error = CardError;
+
+    /// Card at `idx` of [`DeckSpec::STANDARD`].
+    fn try_from(idx: u8) -> Result<Self, Self::Error> {
+        DeckSpec::STANDARD
+            .get(idx)
+            .and_then(DeckCard::card)
+            .ok_or(CardError::InvalidIndex(idx))
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<u8> for Card {
+    /// Index of the card in [`DeckSpec::STANDARD`].
+    fn into(self) -> u8 {
+        match DeckSpec::STANDARD.index(&self.into()) {
+            Some(index) => index,
+            None => unreachable!("the standard deck holds every card"),
+        }
+    }
+}
+
+/// Cards are ordered like their index in [`DeckSpec::STANDARD`].
+impl Ord for Card {
+    fn cmp(&self, other: &Self) -> Ordering {
+        DeckSpec::STANDARD.compare_cards(self, other)
+    }
+}
+
+impl PartialOrd for Card {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Card {
+    /// The cards of `spec` in the order of their index in it.
+    pub fn all_in(spec: &DeckSpec) -> impl Iterator<Item = Card> + '_ {
+        spec.cards()
+    }
+}
+
+impl Display for Card {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} of {}", self.rank, self.suit)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SuitColor {
+    Red,
+    Black,
+}
+
+impl Suit {
+    pub fn color(&self) -> SuitColor {
+        match self {
+            Suit::Diamonds | Suit::Hearts => SuitColor::Red,
+            Suit::Clubs | Suit::Spades => SuitColor::Black,
+        }
+    }
+
+    pub fn symbol(&self) -> char {
+        match self {
+            Suit::Clubs => '♣',
+            Suit::Diamonds => '♦',
+            Suit::Hearts => '♥',
+            Suit::Spades => '♠',
+        }
+    }
+}
+
+impl CardEnum for Suit {
+    const COUNT: u8 = 4;
+}
+
+impl Display for Suit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Suit::Clubs => write!(f, "clubs"),
+            Suit::Diamonds => write!(f, "diamonds"),
+            Suit::Hearts => write!(f, "hearts"),
+            Suit::Spades => write!(f, "spades"),
+        }
+    }
+}
+
+impl TryFrom<u8> for Suit {
+    type Error = SuitError;
+
+    fn try_from(idx: u8) -> Result<Self, Self::Error> {
+        match idx {
+            0 => Ok(Suit::Clubs),
+            1 => Ok(Suit::Diamonds),
+            2 => Ok(Suit::Hearts),
+            3 => Ok(Suit::Spades),
+            _ => Err(SuitError::InvalidIndex(idx)),
+        }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<u8> for Suit {
+    fn into(self) -> u8 {
+        self as u8
+    }
+}
+
+impl TryFrom<&str> for Suit {
+    type Error = SuitError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
+
+impl TryFrom<String> for Suit {
+    type Error = SuitError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(s.as_str())
+    }
+}
+
+impl FromStr for Suit {
+    type Err = SuitError;
+
+    /// Accepts the name, the letter or the symbol of a suit, in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "clubs" | "c" | "♣" => Ok(Suit::Clubs),
+            "diamonds" | "d" | "♦" => Ok(Suit::Diamonds),
+            "hearts" | "h" | "♥" => Ok(Suit::Hearts),
+            "spades" | "s" | "♠" => Ok(Suit::Spades),
+            _ => Err(SuitError::InvalidName(s.into())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rank {
+    #[serde(rename = "2")]
+    Num2,
+    #[serde(rename = "3")]
+    Num3,
+    #[serde(rename = "4")]
+    Num4,
+    #[serde(rename = "5")]
+    Num5,
+    #[serde(rename = "6")]
+    Num6,
+    #[serde(rename = "7")]
+    Num7,
+    #[serde(rename = "8")]
+    Num8,
+    #[serde(rename = "9")]
+    Num9,
+    #[serde(rename = "10")]
+    Num10,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Rank {
+    pub fn symbol(&self) -> char {
+        match self {
+            Rank::Num2 => '2',
+            Rank::Num3 => '3',
+            Rank::Num4 => '4',
+            Rank::Num5 => '5',
+            Rank::Num6 => '6',
+            Rank::Num7 => '7',
+            Rank::Num8 => '8',
+            Rank::Num9 => '9',
+            Rank::Num10 => '0',
+            Rank::Jack => 'J',
+            Rank::Queen => 'Q',
+            Rank::King => 'K',
+            Rank::Ace => 'A',
+        }
+    }
+}
+
+impl CardEnum for Rank {
+    const COUNT: u8 = 13;
+}
+
+impl Display for Rank {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rank::Num2 => write!(f, "2"),
+            Rank::Num3 => write!(f, "3"),
+            Rank::Num4 => write!(f, "4"),
+            Rank::Num5 => write!(f, "5"),
+            Rank::Num6 => write!(f, "6"),
+            Rank::Num7 => write!(f, "7"),
+            Rank::Num8 => write!(f, "8"),
+            Rank::Num9 => write!(f, "9"),
+            Rank::Num10 => write!(f, "10"),
+            Rank::Jack => write!(f, "jack"),
+            Rank::Queen => write!(f, "queen"),
+            Rank::King => write!(f, "king"),
+            Rank::Ace => write!(f, "ace"),
+        }
+    }
+}
+
+impl TryFrom<u8> for Rank {
+    type Error = RankError;
+
+    fn try_from(idx: u8) -> Result<Self, Self::Error> {
+        match idx {
+            0 => Ok(Rank::Num2),
+            1 => Ok(Rank::Num3),
+            2 => Ok(Rank::Num4),
+            3 => Ok(Rank::Num5),
+            4 => Ok(Rank::Num6),
+            5 => Ok(Rank::Num7),
+            6 => Ok(Rank::Num8),
+            7 => Ok(Rank::Num9),
+            8 => Ok(Rank::Num10),
+            9 => Ok(Rank::Jack),
+            10 => Ok(Rank::Queen),
+            11 => Ok(Rank::King),
+            12 => Ok(Rank::Ace),
+            _ => Err(RankError::InvalidIndex(idx)),
+        }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<u8> for Rank {
+    fn into(self) -> u8 {
+        self as u8
+    }
+}
+
+impl TryFrom<&str> for Rank {
+    type Error = RankError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
+
+impl TryFrom<String> for Rank {
+    type Error = RankError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(s.as_str())
+    }
+}
+
+impl FromStr for Rank {
+    type Err = RankError;
+
+    /// Accepts the name or the letter of a rank in any case, ten may be written as "t".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "2" => Ok(Rank::Num2),
+            "3" => Ok(Rank::Num3),
+            "4" => Ok(Rank::Num4),
+            "5" => Ok(Rank::Num5),
+            "6" => Ok(Rank::Num6),
+            "7" => Ok(Rank::Num7),
+            "8" => Ok(Rank::Num8),
+            "9" => Ok(Rank::Num9),
+            "10" | "t" => Ok(Rank::Num10),
+            "jack" | "j" => Ok(Rank::Jack),
+            "queen" | "q" => Ok(Rank::Queen),
+            "king" | "k" => Ok(Rank::King),
+            "ace" | "a" => Ok(Rank::Ace),
+            _ => Err(RankError::InvalidName(s.into())),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnorderedCards {
+    cards: BTreeSet<Card>,
+}
+
+impl UnorderedCards {
+    pub fn new() -> Self {
+        default()
+    }
+
+    pub fn has_suit(&self, suit: Suit) -> bool {
+        !self.is_void(suit)
+    }
+
+    /// Returns the cards which may be played onto a trick led with `led`,
+    /// the led suit has to be followed if possible.
+    pub fn playable(&self, led: Option<Suit>) -> UnorderedCards {
+        match led {
+            Some(suit) if self.has_suit(suit) => self.cards_of_suit(suit),
+            _ => self.clone(),
+        }
+    }
+}
+
+impl CardContainer for UnorderedCards {
+    fn add(&mut self, card: Card) {
+        self.cards.insert(card);
+    }
+
+    fn remove(&mut self, card: &Card) -> bool {
+        self.cards.remove(card)
+    }
+
+    fn contains(&self, card: &Card) -> bool {
+        self.cards.contains(card)
+    }
+
+    fn len(&self) -> u8 {
+        self.cards.len() as u8
+    }
+
+    fn iter(&self) -> impl Iterator<Item = Card> + '_ {
+        self.cards.iter().copied()
+    }
+}
+
+impl FromIterator<Card> for UnorderedCards {
+    fn from_iter<T: IntoIterator<Item = Card>>(iter: T) -> Self {
+        Self {
+            cards: BTreeSet::from_iter(iter),
+        }
+    }
+}
+
+impl IntoIterator for UnorderedCards {
+    type Item = Card;
+    type IntoIter = std::collections::btree_set::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a UnorderedCards {
+    type Item = &'a Card;
+    type IntoIter = std::collections::btree_set::Iter<'a, Card>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.iter()
+    }
+}
+
+impl Extend<Card> for UnorderedCards {
+    fn extend<T: IntoIterator<Item = Card>>(&mut self, iter: T) {
+        for card in iter {
+            self.add(card);
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderedCards {
+    cards: Vec<Card>,
+}
+
+/// Ways to sort a hand for display, ranks are always descending within a suit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortMode {
+    /// Suits in their natural order.
+    Suit,
+    /// Suits alternate between red and black as far as possible, with trumps on the right.
+    Trump(Option<Suit>),
+    /// Suits in the given order, e.g. the one the player arranged the last hand in.
+    Custom([Suit; Suit::COUNT as usize]),
+}
+
+impl OrderedCards {
+    pub fn new() -> Self {
+        default()
+    }
+
+    pub fn sort_by(&mut self, mode: SortMode) {
+        self.sort_in(mode, &DeckSpec::STANDARD);
+    }
+
+    /// Same as [`OrderedCards::sort_by`] with the rank order of `spec`.
+    pub fn sort_in(&mut self, mode: SortMode, spec: &DeckSpec) {
+        let suits = match mode {
+            SortMode::Suit => Suit::all().collect(),
+            SortMode::Custom(suits) => suits.to_vec(),
+            SortMode::Trump(trump) => {
+                let present: Vec<Suit> = Suit::all()
+                    .filter(|suit| self.cards.iter().any(|card| card.suit == *suit))
+                    .collect();
+                alternating_suits(&present, trump)
+            }
+        };
+        self.cards.sort_by_key(|card| {
+            let suit_pos = suits.iter().position(|suit| *suit == card.suit);
+            (suit_pos, std::cmp::Reverse(spec.strength(card.rank)))
+        });
+    }
+
+    pub fn get(&self, index: usize) -> Option<Card> {
+        self.cards.get(index).copied()
+    }
+
+    pub fn position(&self, card: &Card) -> Option<usize> {
+        self.cards.iter().position(|x| x == card)
+    }
+
+    /// Inserts `card` before `index` or at the end, cards already contained are refused.
+    pub fn insert_at(&mut self, index: usize, card: Card) -> bool {
+        if self.cards.contains(&card) {
+            return false;
+        }
+        self.cards.insert(index.min(self.cards.len()), card);
+        true
+    }
+
+    /// Moves the card at `from` so it ends up at `to`, the cards in between shift by one.
+    pub fn move_card(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.cards.len() || to >= self.cards.len() {
+            return false;
+        }
+        let card = self.cards.remove(from);
+        self.cards.insert(to, card);
+        true
+    }
+
+    pub fn swap(&mut self, a: usize, b: usize) -> bool {
+        if a >= self.cards.len() || b >= self.cards.len() {
+            return false;
+        }
+        self.cards.swap(a, b);
+        true
+    }
+
+    /// Removes the cards no longer in `hand` and appends new ones, keeping the order of the rest.
+    pub fn sync_with(&mut self, hand: &impl CardContainer) {
+        self.cards.retain(|card| hand.contains(card));
+        self.extend(hand.iter());
+    }
+
+    /// Suits in the order they first appear, followed by the missing ones.
+    pub fn suit_order(&self) -> [Suit; Suit::COUNT as usize] {
+        let mut order: Vec<Suit> = Vec::with_capacity(Suit::COUNT as usize);
+        for suit in self.cards.iter().map(|card| card.suit).chain(Suit::all()) {
+            if !order.contains(&suit) {
+                order.push(suit);
+            }
+        }
+        [order[0], order[1], order[2], order[3]]
+    }
+}
+
+/// Orders `suits` so that neighbouring suits differ in colour where possible and `trump` comes last.
+///
+/// Ties are broken by the natural suit order.
+fn alternating_suits(suits: &[Suit], trump: Option<Suit>) -> Vec<Suit> {
+    let others: Vec<Suit> = suits
+        .iter()
+        .copied()
+        .filter(|suit| Some(*suit) != trump)
+        .collect();
+    let trump = trump.filter(|trump| suits.contains(trump));
+    let same_color_neighbours = |order: &[Suit]| {
+        order
+            .windows(2)
+            .filter(|pair| pair[0].color() == pair[1].color())
+            .count()
+    };
+    permutations(&others)
+        .into_iter()
+        .map(|mut order| {
+            order.extend(trump);
+            order
+        })
+        .min_by_key(|order| same_color_neighbours(order))
+        .unwrap_or_default()
+}
+
+/// All orders of `suits`, in lexicographic order of their positions.
+fn permutations(suits: &[Suit]) -> Vec<Vec<Suit>> {
+    if suits.is_empty() {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for (i, first) in suits.iter().enumerate() {
+        let mut rest = suits.to_vec();
+        rest.remove(i);
+        for mut order in permutations(&rest) {
+            order.insert(0, *first);
+            result.push(order);
+        }
+    }
+    result
+}
+
+impl CardContainer for OrderedCards {
+    fn add(&mut self, card: Card) {
+        if !self.cards.contains(&card) {
+            self.cards.push(card);
+        }
+    }
+
+    fn remove(&mut self, card: &Card) -> bool {
+        match self.cards.iter().position(|x| x == card) {
+            Some(pos) => {
+                self.cards.remove(pos);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn contains(&self, card: &Card) -> bool {
+        self.cards.contains(card)
+    }
+
+    fn len(&self) -> u8 {
+        self.cards.len() as u8
+    }
+
+    fn iter(&self) -> impl Iterator<Item = Card> + '_ {
+        self.cards.iter().copied()
+    }
+}
+
+impl FromIterator<Card> for OrderedCards {
+    fn from_iter<T: IntoIterator<Item = Card>>(iter: T) -> Self {
+        let mut cards = Self::new();
+        cards.extend(iter);
+        cards
+    }
+}
+
+impl IntoIterator for OrderedCards {
+    type Item = Card;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a OrderedCards {
+    type Item = &'a Card;
+    type IntoIter = std::slice::Iter<'a, Card>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.iter()
+    }
+}
+
+impl Extend<Card> for OrderedCards {
+    fn extend<T: IntoIterator<Item = Card>>(&mut self, iter: T) {
+        for card in iter {
+            self.add(card);
+        }
+    }
+}
+
+#[allow(dead_code)]
+trait CardEnum: TryFrom<u8> {
+    const COUNT: u8;
+
+    fn all() -> Map<Range<u8>, fn(u8) -> Self> {
+        (0..Self::COUNT).map(|i| match i.try_into() {
+            Ok(result) => result,
+            _ => panic!(),
+        })
+    }
+}
+
+#[allow(dead_code)]
+pub trait CardContainer: FromIterator<Card> + IntoIterator<Item = Card> + Extend<Card> {
+    fn all() -> Self {
+        Self::all_in(&DeckSpec::STANDARD)
+    }
+
+    /// The cards of `spec`, without its jokers.
+    fn all_in(spec: &DeckSpec) -> Self {
+        Self::from_iter(Card::all_in(spec))
+    }
+
+    fn add(&mut self, card: Card);
+
+    fn remove(&mut self, card: &Card) -> bool;
+
+    fn contains(&self, card: &Card) -> bool;
+
+    fn len(&self) -> u8;
+
+    fn iter(&self) -> impl Iterator<Item = Card> + '_;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The cards of `suit`, in the order of this container.
+    fn cards_of_suit(&self, suit: Suit) -> Self {
+        self.iter().filter(|card| card.suit == suit).collect()
+    }
+
+    fn highest_in_suit(&self, suit: Suit) -> Option<Card> {
+        self.highest_in_suit_of(suit, &DeckSpec::STANDARD)
+    }
+
+    fn lowest_in_suit(&self, suit: Suit) -> Option<Card> {
+        self.lowest_in_suit_of(suit, &DeckSpec::STANDARD)
+    }
+
+    /// Same as [`CardContainer::highest_in_suit`] with the rank order of `spec`.
+    fn highest_in_suit_of(&self, suit: Suit, spec: &DeckSpec) -> Option<Card> {
+        self.iter()
+            .filter(|card| card.suit == suit)
+            .max_by_key(|card| spec.strength(card.rank))
+    }
+
+    fn lowest_in_suit_of(&self, suit: Suit, spec: &DeckSpec) -> Option<Card> {
+        self.iter()
+            .filter(|card| card.suit == suit)
+            .min_by_key(|card| spec.strength(card.rank))
+    }
+
+    /// Number of cards per suit, indexed like the suits.
+    fn suit_lengths(&self) -> [u8; Suit::COUNT as usize] {
+        let mut lengths = [0; Suit::COUNT as usize];
+        for card in self.iter() {
+            let suit: u8 = card.suit.into();
+            lengths[suit as usize] += 1;
+        }
+        lengths
+    }
+
+    fn is_void(&self, suit: Suit) -> bool {
+        !self.iter().any(|card| card.suit == suit)
+    }
+
+    /// The cards of this container followed by those only in `other`.
+    fn union(&self, other: &impl CardContainer) -> Self {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    fn intersection(&self, other: &impl CardContainer) -> Self {
+        self.iter().filter(|card| other.contains(card)).collect()
+    }
+
+    fn difference(&self, other: &impl CardContainer) -> Self {
+        self.iter().filter(|card| !other.contains(card)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::card::{
+        Card, CardContainer, CardError, OrderedCards, Rank, SortMode, Suit, UnorderedCards,
+    };
+
+    #[test]
+    fn test_card_into_u8() {
+        let card = Card {
+            suit: Suit::Hearts,
+            rank: Rank::Ace,
+        };
+        let actual: u8 = card.into();
+        assert_eq!(38, actual);
+    }
+
+    #[test]
+    fn test_card_try_from_u8() {
+        assert_eq!(Ok("AH".parse().unwrap()), Card::try_from(38));
+        assert_eq!(Err(CardError::InvalidIndex(52)), Card::try_from(52));
+    }
+
+    #[test]
+    fn test_card_format() {
+        let card = Card {
+            suit: Suit::Hearts,
+            rank: Rank::Ace,
+        };
+        assert_eq!("ace of hearts", format!("{card}"));
+    }
+
+    #[test]
+    fn test_card_serialize() {
+        let card = Card {
+            suit: Suit::Hearts,
+            rank: Rank::Ace,
+        };
+        let expected = json!({
+            "suit": "hearts",
+            "rank": "ace",
+        });
+        let actual = serde_json::to_value(card).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_unordered_cards_serialize() {
+        let cards = UnorderedCards::from_iter([
+            Card {
+                suit: Suit::Hearts,
+                rank: Rank::Num2,
+            },
+            Card {
+                suit: Suit::Hearts,
+                rank: Rank::Num4,
+            },
+        ]);
+        let expected = json!({"cards": [
+            {"suit": "hearts", "rank": '2'},
+            {"suit": "hearts", "rank": '4'}
+        ]});
+        let actual = serde_json::to_value(cards).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_ordered_cards_serialize() {
+        let cards = OrderedCards::from_iter([
+            Card {
+                suit: Suit::Hearts,
+                rank: Rank::Num2,
+            },
+            Card {
+                suit: Suit::Hearts,
+                rank: Rank::Num4,
+            },
+        ]);
+        let expected = json!({"cards": [
+            {"suit": "hearts", "rank": '2'},
+            {"suit": "hearts", "rank": '4'}
+        ]});
+        let actual = serde_json::to_value(cards).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_unordered_cards_playable_follow_suit() {
+        let hand = UnorderedCards::from_iter([
+            Card {
+                suit: Suit::Hearts,
+                rank: Rank::Ace,
+            },
+            Card {
+                suit: Suit::Spades,
+                rank: Rank::Num2,
+            },
+        ]);
+        let expected = UnorderedCards::from_iter([Card {
+            suit: Suit::Spades,
+            rank: Rank::Num2,
+        }]);
+        assert_eq!(expected, hand.playable(Some(Suit::Spades)));
+    }
+
+    #[test]
+    fn test_unordered_cards_playable_cannot_follow() {
+        let hand = UnorderedCards::from_iter([
+            Card {
+                suit: Suit::Hearts,
+                rank: Rank::Ace,
+            },
+            Card {
+                suit: Suit::Spades,
+                rank: Rank::Num2,
+            },
+        ]);
+        assert_eq!(hand, hand.playable(Some(Suit::Clubs)));
+        assert_eq!(hand, hand.playable(None));
+    }
+
+    fn cards(cards: &[(Suit, Rank)]) -> OrderedCards {
+        cards
+            .iter()
+            .map(|(suit, rank)| Card {
+                suit: *suit,
+                rank: *rank,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ordered_cards_sort_trump_right() {
+        let mut hand = cards(&[
+            (Suit::Hearts, Rank::Num2),
+            (Suit::Clubs, Rank::Num3),
+            (Suit::Spades, Rank::Ace),
+            (Suit::Hearts, Rank::King),
+            (Suit::Diamonds, Rank::Num9),
+        ]);
+        hand.sort_by(SortMode::Trump(Some(Suit::Spades)));
+        let expected = cards(&[
+            (Suit::Diamonds, Rank::Num9),
+            (Suit::Clubs, Rank::Num3),
+            (Suit::Hearts, Rank::King),
+            (Suit::Hearts, Rank::Num2),
+            (Suit::Spades, Rank::Ace),
+        ]);
+        assert_eq!(expected, hand);
+    }
+
+    #[test]
+    fn test_ordered_cards_sort_alternates_colours() {
+        let mut hand = cards(&[
+            (Suit::Clubs, Rank::Num3),
+            (Suit::Spades, Rank::Num4),
+            (Suit::Hearts, Rank::Num5),
+        ]);
+        hand.sort_by(SortMode::Trump(None));
+        let expected = cards(&[
+            (Suit::Clubs, Rank::Num3),
+            (Suit::Hearts, Rank::Num5),
+            (Suit::Spades, Rank::Num4),
+        ]);
+        assert_eq!(expected, hand);
+
+        hand.sort_by(SortMode::Suit);
+        let expected = cards(&[
+            (Suit::Clubs, Rank::Num3),
+            (Suit::Hearts, Rank::Num5),
+            (Suit::Spades, Rank::Num4),
+        ]);
+        assert_eq!(expected, hand);
+    }
+
+    #[test]
+    fn test_ordered_cards_sort_trump_breaks_colours() {
+        // only hearts can separate the black suits, spades go right as trumps
+        let mut hand = cards(&[
+            (Suit::Clubs, Rank::Num3),
+            (Suit::Spades, Rank::Num4),
+            (Suit::Hearts, Rank::Num5),
+            (Suit::Hearts, Rank::Ace),
+        ]);
+        hand.sort_by(SortMode::Trump(Some(Suit::Spades)));
+        let expected = cards(&[
+            (Suit::Clubs, Rank::Num3),
+            (Suit::Hearts, Rank::Ace),
+            (Suit::Hearts, Rank::Num5),
+            (Suit::Spades, Rank::Num4),
+        ]);
+        assert_eq!(expected, hand);
+    }
+
+    #[test]
+    fn test_container_suit_views() {
+        let hand: UnorderedCards = "S:AK H:T92 D:- C:J".parse().unwrap();
+        assert_eq!(
+            "S:- H:T92 D:- C:-",
+            hand.cards_of_suit(Suit::Hearts).to_string()
+        );
+        assert_eq!(
+            Some("TH".parse().unwrap()),
+            hand.highest_in_suit(Suit::Hearts)
+        );
+        assert_eq!(
+            Some("2H".parse().unwrap()),
+            hand.lowest_in_suit(Suit::Hearts)
+        );
+        assert_eq!(None, hand.highest_in_suit(Suit::Diamonds));
+        assert_eq!([1, 0, 3, 2], hand.suit_lengths());
+        assert!(hand.is_void(Suit::Diamonds));
+        assert!(!hand.is_void(Suit::Clubs));
+        assert!(!hand.is_empty());
+        assert!(UnorderedCards::new().is_empty());
+    }
+
+    #[test]
+    fn test_ordered_cards_of_suit_keeps_order() {
+        let hand: OrderedCards = ["2H", "9H", "AS", "TH"]
+            .iter()
+            .map(|card| card.parse::<Card>().unwrap())
+            .collect();
+        let hearts: Vec<Card> = hand.cards_of_suit(Suit::Hearts).into_iter().collect();
+        let expected: Vec<Card> = ["2H", "9H", "TH"]
+            .iter()
+            .map(|card| card.parse().unwrap())
+            .collect();
+        assert_eq!(expected, hearts);
+    }
+
+    #[test]
+    fn test_container_set_algebra() {
+        let hand: OrderedCards = "S:A H:2 C:3".parse().unwrap();
+        let other: UnorderedCards = "H:2 D:4".parse().unwrap();
+        assert_eq!("S:A H:2 D:4 C:3", hand.union(&other).to_string());
+        assert_eq!("S:- H:2 D:- C:-", hand.intersection(&other).to_string());
+        assert_eq!("S:A H:- D:- C:3", hand.difference(&other).to_string());
+        let union: Vec<Card> = hand.union(&other).into_iter().collect();
+        assert_eq!(Some(&"4D".parse().unwrap()), union.last());
+    }
+
+    #[test]
+    fn test_ordered_cards_rearrange() {
+        let mut hand: OrderedCards = "S:A H:K D:Q".parse().unwrap();
+        let king: Card = "KH".parse().unwrap();
+        assert!(!hand.insert_at(0, king));
+        assert!(hand.insert_at(1, "2C".parse().unwrap()));
+        assert!(hand.move_card(0, 3));
+        assert!(hand.swap(0, 1));
+        assert!(!hand.move_card(0, 4));
+        let cards: Vec<String> = hand.iter().map(|card| card.short()).collect();
+        assert_eq!(vec!["KH", "2C", "QD", "AS"], cards);
+        assert_eq!(Some(0), hand.position(&king));
+        assert_eq!(Some(king), hand.get(0));
+    }
+
+    #[test]
+    fn test_ordered_cards_sync_keeps_arrangement() {
+        let mut hand: OrderedCards = "D:Q S:A H:K".parse().unwrap();
+        let dealt: UnorderedCards = "S:A D:Q C:3".parse().unwrap();
+        hand.sync_with(&dealt);
+        let cards: Vec<String> = hand.iter().map(|card| card.short()).collect();
+        assert_eq!(vec!["QD", "AS", "3C"], cards);
+    }
+
+    #[test]
+    fn test_ordered_cards_sort_custom_suit_order() {
+        let mut hand: OrderedCards = ["2H", "AS", "KC", "AH"]
+            .iter()
+            .map(|card| card.parse::<Card>().unwrap())
+            .collect();
+        let order = hand.suit_order();
+        assert_eq!(
+            [Suit::Hearts, Suit::Spades, Suit::Clubs, Suit::Diamonds],
+            order
+        );
+        let mut other: OrderedCards = "C:2 D:3 S:4 H:5".parse().unwrap();
+        other.sort_by(SortMode::Custom(order));
+        let cards: Vec<String> = other.iter().map(|card| card.short()).collect();
+        assert_eq!(vec!["5H", "4S", "2C", "3D"], cards);
+        hand.sort_by(SortMode::Custom(order));
+        let cards: Vec<String> = hand.iter().map(|card| card.short()).collect();
+        assert_eq!(vec!["AH", "2H", "AS", "KC"], cards);
+    }
+}
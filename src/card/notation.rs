@@ -0,0 +1,265 @@
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::card::{Card, CardError, OrderedCards, Rank, Suit, UnorderedCards};
+
+/// Order of the suits in hand notation, as used by bridge.
+const NOTATION_SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+const VOID: &str = "-";
+
+impl Suit {
+    pub fn letter(&self) -> char {
+        match self {
+            Suit::Clubs => 'C',
+            Suit::Diamonds => 'D',
+            Suit::Hearts => 'H',
+            Suit::Spades => 'S',
+        }
+    }
+}
+
+impl Rank {
+    /// Single character of the rank, ten is written as "T".
+    pub fn letter(&self) -> char {
+        match self {
+            Rank::Num10 => 'T',
+            _ => self.symbol(),
+        }
+    }
+}
+
+impl Card {
+    /// Short notation like "AH" or "TS".
+    pub fn short(&self) -> String {
+        format!("{}{}", self.rank.letter(), self.suit.letter())
+    }
+}
+
+impl FromStr for Card {
+    type Err = CardError;
+
+    /// Parses short notation like "AH", "10s", "ts" or "Q♦", the suit always comes last.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let Some((split, _)) = s.char_indices().last().filter(|(split, _)| *split > 0) else {
+            return Err(CardError::InvalidNotation(s.into()));
+        };
+        Ok(Card {
+            rank: s[..split].parse()?,
+            suit: s[split..].parse()?,
+        })
+    }
+}
+
+/// Writes cards grouped by suit, like "S:AKQ H:T92 D:- C:J8765".
+fn write_hand<'a>(
+    f: &mut Formatter<'_>,
+    cards: impl IntoIterator<Item = &'a Card>,
+) -> std::fmt::Result {
+    let mut cards: Vec<&Card> = cards.into_iter().collect();
+    cards.sort_by_key(|card| Reverse(card.rank));
+    for (i, suit) in NOTATION_SUITS.iter().enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
+        }
+        write!(f, "{}:", suit.letter())?;
+        let ranks: String = cards
+            .iter()
+            .filter(|card| card.suit == *suit)
+            .map(|card| card.rank.letter())
+            .collect();
+        if ranks.is_empty() {
+            write!(f, "{VOID}")?;
+        } else {
+            write!(f, "{ranks}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads cards written by suit, suits may be left out and ten may also be written as "10".
+///
+/// Every suit and card may only be written once.
+fn parse_hand(s: &str) -> Result<Vec<Card>, CardError> {
+    let mut cards = Vec::new();
+    let mut suits = Vec::new();
+    for group in s.split_whitespace() {
+        let Some((suit, ranks)) = group.split_once(':') else {
+            return Err(CardError::InvalidNotation(group.into()));
+        };
+        let suit: Suit = suit.parse()?;
+        if suits.contains(&suit) {
+            return Err(CardError::InvalidNotation(group.into()));
+        }
+        suits.push(suit);
+        if ranks == VOID {
+            continue;
+        }
+        // a void is always written out, so every hand has a single spelling
+        if ranks.is_empty() {
+            return Err(CardError::InvalidNotation(group.into()));
+        }
+        let mut chars = ranks.chars().peekable();
+        while let Some(c) = chars.next() {
+            let rank: Rank = if c == '1' && chars.next_if_eq(&'0').is_some() {
+                Rank::Num10
+            } else {
+                c.to_string().parse()?
+            };
+            let card = Card { suit, rank };
+            if cards.contains(&card) {
+                return Err(CardError::InvalidNotation(group.into()));
+            }
+            cards.push(card);
+        }
+    }
+    Ok(cards)
+}
+
+impl Display for UnorderedCards {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_hand(f, self)
+    }
+}
+
+impl FromStr for UnorderedCards {
+    type Err = CardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_hand(s)?.into_iter().collect())
+    }
+}
+
+/// Written like [`UnorderedCards`], the order is not part of the notation.
+impl Display for OrderedCards {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_hand(f, self)
+    }
+}
+
+/// Keeps the cards in the order they are written.
+impl FromStr for OrderedCards {
+    type Err = CardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_hand(s)?.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::card::*;
+
+    const QUEEN_OF_DIAMONDS: Card = Card {
+        suit: Suit::Diamonds,
+        rank: Rank::Queen,
+    };
+
+    #[test]
+    fn test_card_parse_short() {
+        for text in ["QD", "qd", "Q♦", " qD "] {
+            assert_eq!(Ok(QUEEN_OF_DIAMONDS), text.parse(), "{text}");
+        }
+        let ten_of_spades = Card {
+            suit: Suit::Spades,
+            rank: Rank::Num10,
+        };
+        for text in ["10S", "TS", "ts", "10♠"] {
+            assert_eq!(Ok(ten_of_spades), text.parse(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_card_parse_invalid() {
+        assert_eq!(
+            Err(CardError::InvalidNotation("A".to_string())),
+            "A".parse::<Card>()
+        );
+        assert_eq!(
+            Err(CardError::Rank(RankError::InvalidName("1".to_string()))),
+            "1H".parse::<Card>()
+        );
+        assert_eq!(
+            Err(CardError::Suit(SuitError::InvalidName("X".to_string()))),
+            "AX".parse::<Card>()
+        );
+    }
+
+    #[test]
+    fn test_card_short_round_trip() {
+        for card in (0..52).map(|index| Card::try_from(index).unwrap()) {
+            assert_eq!(Ok(card), card.short().parse());
+        }
+        assert_eq!("QD", QUEEN_OF_DIAMONDS.short());
+    }
+
+    #[test]
+    fn test_suit_and_rank_parse_long_names() {
+        assert_eq!(Ok(Suit::Hearts), "Hearts".parse());
+        assert_eq!(Ok(Rank::Queen), "QUEEN".parse());
+    }
+
+    #[test]
+    fn test_hand_notation() {
+        let hand: UnorderedCards = "S:AKQ H:T92 D:- C:J8765".parse().unwrap();
+        assert_eq!(11, hand.len());
+        assert!(hand.contains(&Card {
+            suit: Suit::Hearts,
+            rank: Rank::Num10
+        }));
+        assert_eq!("S:AKQ H:T92 D:- C:J8765", hand.to_string());
+
+        let hand: UnorderedCards = "h:109 c:a".parse().unwrap();
+        assert_eq!("S:- H:T9 D:- C:A", hand.to_string());
+        assert_eq!("S:- H:- D:- C:-", UnorderedCards::new().to_string());
+    }
+
+    #[test]
+    fn test_ordered_hand_notation_keeps_order() {
+        let hand: OrderedCards = "C:2 S:A".parse().unwrap();
+        let cards: Vec<String> = hand.clone().into_iter().map(|card| card.short()).collect();
+        assert_eq!(vec!["2C", "AS"], cards);
+        assert_eq!("S:A H:- D:- C:2", hand.to_string());
+    }
+
+    #[test]
+    fn test_hand_notation_invalid() {
+        assert_eq!(
+            Err(CardError::InvalidNotation("AKQ".to_string())),
+            "AKQ".parse::<UnorderedCards>()
+        );
+        assert_eq!(
+            Err(CardError::Rank(RankError::InvalidName("X".to_string()))),
+            "S:AX".parse::<UnorderedCards>()
+        );
+    }
+
+    #[test]
+    fn test_hand_notation_rejects_empty_suit() {
+        assert_eq!(
+            Err(CardError::InvalidNotation("H:".to_string())),
+            "S:A H: D:- C:-".parse::<UnorderedCards>()
+        );
+        let hand: UnorderedCards = "S:A H:- D:- C:-".parse().unwrap();
+        assert_eq!(Ok(hand.clone()), hand.to_string().parse());
+    }
+
+    #[test]
+    fn test_hand_notation_rejects_duplicates() {
+        assert_eq!(
+            Err(CardError::InvalidNotation("S:AA".to_string())),
+            "S:AA".parse::<UnorderedCards>()
+        );
+        assert_eq!(
+            Err(CardError::InvalidNotation("S:K".to_string())),
+            "S:A S:K".parse::<OrderedCards>()
+        );
+        assert_eq!(
+            Err(CardError::InvalidNotation("H:T10".to_string())),
+            "H:T10".parse::<UnorderedCards>()
+        );
+    }
+}
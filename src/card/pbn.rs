@@ -0,0 +1,354 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::card::{Card, CardContainer, Rank, Suit, Trick, UnorderedCards};
+
+/// PBN describes bridge, so only games with four seats can be written.
+pub const PBN_SEATS: u8 = 4;
+/// Seats in clockwise order, seat 0 is north.
+const DIRECTIONS: [char; PBN_SEATS as usize] = ['N', 'E', 'S', 'W'];
+/// Order of the suits within a hand of the deal.
+const DEAL_SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+const NO_TRUMP: &str = "NT";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PbnError {
+    MissingTag(&'static str),
+    InvalidDirection(String),
+    InvalidDeal(String),
+    InvalidTrump(String),
+    InvalidCard(String),
+    /// A card was played by a seat that did not hold it.
+    NotInHand(Card),
+}
+
+impl Display for PbnError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PbnError::MissingTag(tag) => write!(f, "the {tag} tag is missing"),
+            PbnError::InvalidDirection(s) => write!(f, "{s:?} is not a direction"),
+            PbnError::InvalidDeal(s) => write!(f, "{s:?} is not a deal"),
+            PbnError::InvalidTrump(s) => write!(f, "{s:?} is not a trump"),
+            PbnError::InvalidCard(s) => write!(f, "{s:?} is not a card"),
+            PbnError::NotInHand(card) => write!(f, "the {card} was played but never dealt"),
+        }
+    }
+}
+
+impl std::error::Error for PbnError {}
+
+/// A deal of four hands and the tricks played from it, in Portable Bridge Notation.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PbnGame {
+    pub dealer: u8,
+    pub trump: Option<Suit>,
+    /// Hands indexed by seat as they were dealt, empty if unknown.
+    pub hands: [UnorderedCards; PBN_SEATS as usize],
+    /// Tricks in the order they were played, the last one may be incomplete.
+    pub tricks: Vec<Trick>,
+}
+
+fn direction(seat: u8) -> char {
+    DIRECTIONS[(seat % PBN_SEATS) as usize]
+}
+
+fn parse_direction(s: &str) -> Result<u8, PbnError> {
+    let mut chars = s.trim().chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => DIRECTIONS
+            .iter()
+            .position(|direction| *direction == c.to_ascii_uppercase())
+            .map(|seat| seat as u8)
+            .ok_or_else(|| PbnError::InvalidDirection(s.into())),
+        _ => Err(PbnError::InvalidDirection(s.into())),
+    }
+}
+
+/// PBN writes the suit first, like "SA".
+fn write_card(f: &mut Formatter<'_>, card: &Card) -> std::fmt::Result {
+    write!(f, "{}{}", card.suit.letter(), card.rank.letter())
+}
+
+fn parse_card(s: &str) -> Result<Card, PbnError> {
+    let mut chars = s.chars();
+    let suit = chars.next().and_then(|suit| suit.to_string().parse().ok());
+    let rank = chars.as_str().parse().ok();
+    match (suit, rank) {
+        (Some(suit), Some(rank)) => Ok(Card { suit, rank }),
+        _ => Err(PbnError::InvalidCard(s.into())),
+    }
+}
+
+/// A hand like "AKQ.T92..J8765", spades first.
+fn write_hand(f: &mut Formatter<'_>, hand: &UnorderedCards) -> std::fmt::Result {
+    if hand.is_empty() {
+        return write!(f, "-");
+    }
+    for (i, suit) in DEAL_SUITS.iter().enumerate() {
+        if i > 0 {
+            write!(f, ".")?;
+        }
+        let mut cards: Vec<Card> = hand.cards_of_suit(*suit).into_iter().collect();
+        cards.reverse();
+        for card in cards {
+            write!(f, "{}", card.rank.letter())?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_hand(s: &str) -> Result<UnorderedCards, PbnError> {
+    if s == "-" {
+        return Ok(UnorderedCards::new());
+    }
+    let suits: Vec<&str> = s.split('.').collect();
+    if suits.len() != DEAL_SUITS.len() {
+        return Err(PbnError::InvalidDeal(s.into()));
+    }
+    let mut hand = UnorderedCards::new();
+    for (suit, ranks) in DEAL_SUITS.iter().zip(suits) {
+        for rank in ranks.chars() {
+            let rank: Rank = rank
+                .to_string()
+                .parse()
+                .map_err(|_| PbnError::InvalidDeal(s.into()))?;
+            let card = Card { suit: *suit, rank };
+            if hand.contains(&card) {
+                return Err(PbnError::InvalidDeal(s.into()));
+            }
+            hand.add(card);
+        }
+    }
+    Ok(hand)
+}
+
+/// Tag pairs in the order they appear, with the lines following each tag.
+fn tags(s: &str) -> Vec<(&str, &str, Vec<&str>)> {
+    let mut tags: Vec<(&str, &str, Vec<&str>)> = Vec::new();
+    for line in s.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('%') || line.starts_with(';') {
+            continue;
+        }
+        let tag = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+            .and_then(|line| line.split_once(' '))
+            .map(|(name, value)| (name, value.trim().trim_matches('"')));
+        match (tag, tags.last_mut()) {
+            (Some((name, value)), _) => tags.push((name, value, Vec::new())),
+            (None, Some((_, _, lines))) => lines.push(line),
+            (None, None) => {}
+        }
+    }
+    tags
+}
+
+impl Display for PbnGame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Dealer \"{}\"]", direction(self.dealer))?;
+        write!(f, "[Deal \"{}:", direction(self.dealer))?;
+        for i in 0..PBN_SEATS {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write_hand(f, &self.hands[((self.dealer + i) % PBN_SEATS) as usize])?;
+        }
+        writeln!(f, "\"]")?;
+        match self.trump {
+            Some(suit) => writeln!(f, "[Trump \"{}\"]", suit.letter())?,
+            None => writeln!(f, "[Trump \"{NO_TRUMP}\"]")?,
+        }
+        let Some(leader) = self.tricks.first().and_then(Trick::leader) else {
+            return Ok(());
+        };
+        // every column belongs to one seat, starting with the first leader
+        writeln!(f, "[Play \"{}\"]", direction(leader))?;
+        for trick in &self.tricks {
+            for i in 0..PBN_SEATS {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                match trick.card_of((leader + i) % PBN_SEATS) {
+                    Some(card) => write_card(f, &card)?,
+                    None => write!(f, "-")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "*")
+    }
+}
+
+impl FromStr for PbnGame {
+    type Err = PbnError;
+
+    /// Reads the Dealer, Deal, Trump and Play tags, other tags are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tags = tags(s);
+        let tag = |name: &'static str| {
+            tags.iter()
+                .find(|(tag, _, _)| *tag == name)
+                .ok_or(PbnError::MissingTag(name))
+        };
+
+        let (_, deal, _) = tag("Deal")?;
+        let (first, hands) = deal
+            .split_once(':')
+            .ok_or_else(|| PbnError::InvalidDeal(deal.to_string()))?;
+        let first = parse_direction(first)?;
+        let hands: Vec<&str> = hands.split_whitespace().collect();
+        if hands.len() != PBN_SEATS as usize {
+            return Err(PbnError::InvalidDeal(deal.to_string()));
+        }
+        let mut game = PbnGame {
+            dealer: match tag("Dealer") {
+                Ok((_, dealer, _)) => parse_direction(dealer)?,
+                Err(_) => first,
+            },
+            ..Self::default()
+        };
+        let mut dealt = UnorderedCards::new();
+        for (i, hand) in hands.into_iter().enumerate() {
+            let hand = parse_hand(hand)?;
+            if !hand.intersection(&dealt).is_empty() {
+                return Err(PbnError::InvalidDeal(deal.to_string()));
+            }
+            dealt.extend(hand.iter());
+            game.hands[(first as usize + i) % PBN_SEATS as usize] = hand;
+        }
+        game.trump = match tag("Trump") {
+            Ok((_, trump, _)) if trump.eq_ignore_ascii_case(NO_TRUMP) => None,
+            Ok((_, trump, _)) => Some(
+                trump
+                    .parse()
+                    .map_err(|_| PbnError::InvalidTrump(trump.to_string()))?,
+            ),
+            Err(_) => None,
+        };
+
+        let Ok((_, leader, lines)) = tag("Play") else {
+            return Ok(game);
+        };
+        let mut leader = parse_direction(leader)?;
+        let first_leader = leader;
+        let mut played = UnorderedCards::new();
+        for line in lines {
+            let columns: Vec<&str> = line
+                .split_whitespace()
+                .take_while(|column| *column != "*")
+                .collect();
+            if columns.is_empty() {
+                continue;
+            }
+            let mut trick = Trick::new();
+            for i in 0..PBN_SEATS {
+                let seat = (leader + i) % PBN_SEATS;
+                let column = (seat + PBN_SEATS - first_leader) % PBN_SEATS;
+                match columns.get(column as usize) {
+                    Some(&"-") | None => {}
+                    Some(column) => {
+                        let card = parse_card(column)?;
+                        if played.contains(&card) {
+                            return Err(PbnError::InvalidCard(column.to_string()));
+                        }
+                        played.add(card);
+                        let hand = &game.hands[seat as usize];
+                        // unknown hands are empty
+                        if !hand.is_empty() && !hand.contains(&card) {
+                            return Err(PbnError::NotInHand(card));
+                        }
+                        trick.play(seat, card);
+                    }
+                }
+            }
+            if let Some(winner) = trick.winner(game.trump) {
+                leader = winner;
+            }
+            game.tricks.push(trick);
+        }
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const PLAYED: &str = include_str!("fixtures/no_trump.pbn");
+    const DEAL_ONLY: &str = include_str!("fixtures/deal_only.pbn");
+
+    #[test]
+    fn test_round_trip_played_hand() {
+        let game: PbnGame = PLAYED.parse().unwrap();
+        assert_eq!(13, game.tricks.len());
+        assert!(game.tricks.iter().all(|trick| trick.len() == 4));
+        assert_eq!(PLAYED, game.to_string());
+    }
+
+    #[test]
+    fn test_round_trip_deal_only() {
+        let game: PbnGame = DEAL_ONLY.parse().unwrap();
+        assert_eq!(2, game.dealer);
+        assert_eq!(Some(Suit::Hearts), game.trump);
+        assert_eq!("S:AKQ H:T92 D:- C:J8765432", game.hands[2].to_string());
+        assert!(game.hands[1].is_empty());
+        assert_eq!(DEAL_ONLY, game.to_string());
+    }
+
+    #[test]
+    fn test_play_columns_belong_to_seats() {
+        let text = "[Deal \"N:A... K... Q... J...\"]\n[Play \"E\"]\nSK SQ SJ SA\n*\n";
+        let game: PbnGame = text.parse().unwrap();
+        let seats: Vec<u8> = game.tricks[0]
+            .plays()
+            .iter()
+            .map(|play| play.seat)
+            .collect();
+        assert_eq!(vec![1, 2, 3, 0], seats);
+        assert_eq!(Some(0), game.tricks[0].winner(None));
+        assert_eq!(0, game.dealer);
+    }
+
+    #[test]
+    fn test_ignores_other_tags_and_comments() {
+        let text = "% exported\n[Event \"club night\"]\n[Deal \"W:- - - -\"]\n";
+        let game: PbnGame = text.parse().unwrap();
+        assert_eq!(3, game.dealer);
+        assert_eq!(None, game.trump);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(Err(PbnError::MissingTag("Deal")), "".parse::<PbnGame>());
+        assert_eq!(
+            Err(PbnError::InvalidDirection("X".to_string())),
+            "[Deal \"X:- - - -\"]".parse::<PbnGame>()
+        );
+        assert_eq!(
+            Err(PbnError::InvalidDeal("N:AK.Q".to_string())),
+            "[Deal \"N:AK.Q\"]".parse::<PbnGame>()
+        );
+        assert_eq!(
+            Err(PbnError::InvalidTrump("X".to_string())),
+            "[Deal \"N:- - - -\"]\n[Trump \"X\"]".parse::<PbnGame>()
+        );
+    }
+
+    #[test]
+    fn test_rejects_duplicate_cards() {
+        assert_eq!(
+            Err(PbnError::InvalidDeal("AA...".to_string())),
+            "[Deal \"N:AA... - - -\"]".parse::<PbnGame>()
+        );
+        assert_eq!(
+            Err(PbnError::InvalidDeal("N:A... A... - -".to_string())),
+            "[Deal \"N:A... A... - -\"]".parse::<PbnGame>()
+        );
+        assert_eq!(
+            Err(PbnError::InvalidCard("SA".to_string())),
+            "[Deal \"N:- - - -\"]\n[Play \"N\"]\nSA SK SQ SJ\nSA S2 S3 S4\n*\n".parse::<PbnGame>()
+        );
+    }
+}
@@ -0,0 +1,512 @@
+use serde::{Deserialize, Serialize};
+
+use crate::card::{
+    Bid, Card, CardContainer, DeckSpec, OrderedCards, Suit, TEAM_COUNT, UnorderedCards, VariantKind,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RuleError {
+    RoundFinished,
+    NotYourTurn {
+        expected: u8,
+        actual: u8,
+    },
+    /// The seat does not take part in the round.
+    InvalidSeat(u8),
+    NotInHand(Card),
+    MustFollowSuit(Suit),
+    /// The card is in hand but the variant does not allow it now.
+    IllegalCard(Card),
+}
+
+/// A card played by a seat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Play {
+    pub seat: u8,
+    pub card: Card,
+}
+
+/// The cards of a trick in the order they were played, the first card leads.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Trick {
+    plays: Vec<Play>,
+}
+
+impl Trick {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn play(&mut self, seat: u8, card: Card) {
+        self.plays.push(Play { seat, card });
+    }
+
+    pub fn plays(&self) -> &[Play] {
+        &self.plays
+    }
+
+    pub fn len(&self) -> usize {
+        self.plays.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plays.is_empty()
+    }
+
+    pub fn leader(&self) -> Option<u8> {
+        self.plays.first().map(|play| play.seat)
+    }
+
+    pub fn led_suit(&self) -> Option<Suit> {
+        self.plays.first().map(|play| play.card.suit)
+    }
+
+    pub fn card_of(&self, seat: u8) -> Option<Card> {
+        self.plays
+            .iter()
+            .find(|play| play.seat == seat)
+            .map(|play| play.card)
+    }
+
+    /// Returns the seat currently winning the trick.
+    ///
+    /// The highest trump wins, without trumps the highest card of the led suit.
+    pub fn winner(&self, trump: Option<Suit>) -> Option<u8> {
+        self.winner_by(|card, other, led| beats(card, other, led, trump))
+    }
+
+    /// Same as [`Trick::winner`] but with a custom order,
+    /// `beats(card, other, led)` tells whether `card` beats `other`.
+    pub fn winner_by(&self, beats: impl Fn(Card, Card, Suit) -> bool) -> Option<u8> {
+        let led = self.led_suit()?;
+        self.plays
+            .iter()
+            .reduce(|best, play| {
+                if beats(play.card, best.card, led) {
+                    play
+                } else {
+                    best
+                }
+            })
+            .map(|play| play.seat)
+    }
+}
+
+/// Returns whether `card` beats `other` in a trick led with `led`.
+pub fn beats(card: Card, other: Card, led: Suit, trump: Option<Suit>) -> bool {
+    DeckSpec::STANDARD.beats(card, other, led, trump)
+}
+
+/// Why the variant refused `card`, only following suit has an error of its own.
+fn illegal_play(hand: &UnorderedCards, trick: &Trick, card: Card) -> RuleError {
+    match trick.led_suit() {
+        Some(led) if card.suit != led && hand.has_suit(led) => RuleError::MustFollowSuit(led),
+        _ => RuleError::IllegalCard(card),
+    }
+}
+
+/// One hand of whist from the deal until every card has been played.
+///
+/// The server runs the online game, this engine is for bots and offline play.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    variant: VariantKind,
+    /// Winning bid of the auction, if the variant has one.
+    contract: Option<Bid>,
+    trump: Option<Suit>,
+    /// Remaining cards indexed by seat.
+    hands: Vec<UnorderedCards>,
+    trick: Trick,
+    turn: u8,
+    /// Completed tricks together with the seat that took them.
+    tricks: Vec<(Trick, u8)>,
+    /// Cards drawn after each trick, the first card is on top.
+    stock: OrderedCards,
+}
+
+impl Round {
+    /// Starts a round, `leader` plays the first card.
+    pub fn new(hands: Vec<UnorderedCards>, leader: u8, trump: Option<Suit>) -> Self {
+        Self {
+            variant: VariantKind::default(),
+            contract: None,
+            trump,
+            hands,
+            trick: Trick::new(),
+            turn: leader,
+            tricks: Vec::new(),
+            stock: OrderedCards::new(),
+        }
+    }
+
+    /// Plays the round with the rules of `variant` under the winning `contract`.
+    pub fn with_variant(mut self, variant: VariantKind, contract: Option<Bid>) -> Self {
+        self.variant = variant;
+        self.contract = contract;
+        self
+    }
+
+    /// Adds a stock to draw from, only used by variants which draw after each trick.
+    pub fn with_stock(mut self, stock: OrderedCards) -> Self {
+        self.stock = stock;
+        self
+    }
+
+    pub fn variant(&self) -> VariantKind {
+        self.variant
+    }
+
+    pub fn contract(&self) -> Option<&Bid> {
+        self.contract.as_ref()
+    }
+
+    pub fn stock(&self) -> &OrderedCards {
+        &self.stock
+    }
+
+    pub fn seats(&self) -> u8 {
+        self.hands.len() as u8
+    }
+
+    pub fn trump(&self) -> Option<Suit> {
+        self.trump
+    }
+
+    pub fn turn(&self) -> u8 {
+        self.turn
+    }
+
+    pub fn trick(&self) -> &Trick {
+        &self.trick
+    }
+
+    pub fn hand(&self, seat: u8) -> Option<&UnorderedCards> {
+        self.hands.get(seat as usize)
+    }
+
+    pub fn tricks(&self) -> &[(Trick, u8)] {
+        &self.tricks
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.trick.is_empty() && self.hands.iter().all(|hand| hand.len() == 0)
+    }
+
+    pub fn legal_plays(&self) -> UnorderedCards {
+        match self.hand(self.turn) {
+            Some(hand) => self.variant.rules().legal_plays(hand, &self.trick),
+            None => UnorderedCards::new(),
+        }
+    }
+
+    /// Plays a card for `seat` and returns the winner if it completed the trick.
+    ///
+    /// The winner of a trick leads the next one.
+    pub fn play(&mut self, seat: u8, card: Card) -> Result<Option<u8>, RuleError> {
+        if self.is_finished() {
+            return Err(RuleError::RoundFinished);
+        }
+        if seat != self.turn {
+            return Err(RuleError::NotYourTurn {
+                expected: self.turn,
+                actual: seat,
+            });
+        }
+        let Some(hand) = self.hands.get_mut(seat as usize) else {
+            return Err(RuleError::InvalidSeat(seat));
+        };
+        if !hand.contains(&card) {
+            return Err(RuleError::NotInHand(card));
+        }
+        let rules = self.variant.rules();
+        if !rules.legal_plays(hand, &self.trick).contains(&card) {
+            return Err(illegal_play(hand, &self.trick, card));
+        }
+        hand.remove(&card);
+        self.trick.play(seat, card);
+
+        if self.trick.len() < self.hands.len() {
+            self.turn = (seat + 1) % self.seats();
+            return Ok(None);
+        }
+        let trick = std::mem::take(&mut self.trick);
+        // a complete trick is never empty
+        let winner = rules
+            .trick_winner(&trick, self.trump, self.contract.as_ref())
+            .unwrap();
+        self.tricks.push((trick, winner));
+        self.turn = winner;
+        if rules.draws_from_stock() {
+            self.draw(winner);
+        }
+        Ok(Some(winner))
+    }
+
+    /// Every player draws a card from the stock, starting with the winner of the trick.
+    fn draw(&mut self, winner: u8) {
+        for i in 0..self.seats() {
+            let seat = (winner + i) % self.seats();
+            let Some(card) = (&self.stock).into_iter().next().copied() else {
+                return;
+            };
+            self.stock.remove(&card);
+            self.hands[seat as usize].add(card);
+        }
+    }
+
+    pub fn tricks_won(&self, seat: u8) -> usize {
+        self.tricks
+            .iter()
+            .filter(|(_, winner)| *winner == seat)
+            .count()
+    }
+
+    /// Number of tricks taken by each team, indexed by team.
+    ///
+    /// `None` if the variant is not played in partnerships.
+    pub fn team_tricks(&self) -> Option<[usize; TEAM_COUNT as usize]> {
+        let rules = self.variant.rules();
+        // partnerships are fixed for the whole table, so the first seat tells
+        rules.team(0)?;
+        let mut counts = [0; TEAM_COUNT as usize];
+        for (_, winner) in &self.tricks {
+            counts[rules.team(*winner)? as usize] += 1;
+        }
+        Some(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde::Deserialize;
+
+    use super::illegal_play;
+    use crate::card::*;
+
+    const SUITS: [Option<Suit>; 5] = [
+        None,
+        Some(Suit::Clubs),
+        Some(Suit::Diamonds),
+        Some(Suit::Hearts),
+        Some(Suit::Spades),
+    ];
+
+    fn card(suit: Suit, rank: Rank) -> Card {
+        Card { suit, rank }
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct FixtureTrick {
+        plays: Vec<Play>,
+        winner: u8,
+    }
+
+    /// A complete recorded game, checked play by play against the engine.
+    #[derive(Debug, Deserialize)]
+    struct Fixture {
+        trump: Option<Suit>,
+        leader: u8,
+        hands: Vec<Vec<Card>>,
+        tricks: Vec<FixtureTrick>,
+        team_tricks: [usize; 2],
+    }
+
+    fn check_fixture(json: &str) {
+        let fixture: Fixture = serde_json::from_str(json).unwrap();
+        let hands = fixture
+            .hands
+            .into_iter()
+            .map(UnorderedCards::from_iter)
+            .collect();
+        let mut round = Round::new(hands, fixture.leader, fixture.trump);
+        for trick in fixture.tricks {
+            let (last, plays) = trick.plays.split_last().unwrap();
+            for play in plays {
+                assert!(round.legal_plays().contains(&play.card));
+                assert_eq!(Ok(None), round.play(play.seat, play.card));
+            }
+            assert_eq!(Ok(Some(trick.winner)), round.play(last.seat, last.card));
+            assert_eq!(trick.winner, round.turn());
+        }
+        assert!(round.is_finished());
+        assert_eq!(Some(fixture.team_tricks), round.team_tricks());
+    }
+
+    #[test]
+    fn test_fixture_no_trump() {
+        check_fixture(include_str!("fixtures/no_trump.json"));
+    }
+
+    #[test]
+    fn test_fixture_hearts_trump() {
+        check_fixture(include_str!("fixtures/hearts_trump.json"));
+    }
+
+    #[test]
+    fn test_winner_every_pair() {
+        for trump in SUITS {
+            for first in Card::all() {
+                for second in Card::all().filter(|c| *c != first) {
+                    let mut trick = Trick::new();
+                    trick.play(0, first);
+                    trick.play(1, second);
+                    let second_wins = if second.suit == first.suit {
+                        second.rank > first.rank
+                    } else {
+                        Some(second.suit) == trump
+                    };
+                    let expected = if second_wins { 1 } else { 0 };
+                    assert_eq!(Some(expected), trick.winner(trump), "{first} {second}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_winner_empty_trick() {
+        assert_eq!(None, Trick::new().winner(Some(Suit::Hearts)));
+    }
+
+    #[test]
+    fn test_winner_trump_over_led_suit() {
+        let mut trick = Trick::new();
+        trick.play(2, card(Suit::Spades, Rank::Ace));
+        trick.play(3, card(Suit::Hearts, Rank::Num2));
+        trick.play(0, card(Suit::Hearts, Rank::Num5));
+        trick.play(1, card(Suit::Clubs, Rank::Ace));
+        assert_eq!(Some(0), trick.winner(Some(Suit::Hearts)));
+        assert_eq!(Some(2), trick.winner(None));
+    }
+
+    #[test]
+    fn test_legal_plays_must_follow() {
+        let hand = UnorderedCards::from_iter([
+            card(Suit::Spades, Rank::Num3),
+            card(Suit::Hearts, Rank::King),
+        ]);
+        let mut trick = Trick::new();
+        assert_eq!(hand, ClassicWhist.legal_plays(&hand, &trick));
+        trick.play(0, card(Suit::Hearts, Rank::Num2));
+        assert_eq!(
+            UnorderedCards::from_iter([card(Suit::Hearts, Rank::King)]),
+            ClassicWhist.legal_plays(&hand, &trick)
+        );
+    }
+
+    #[test]
+    fn test_round_rejects_invalid_plays() {
+        let hands = vec![
+            UnorderedCards::from_iter([
+                card(Suit::Spades, Rank::Num3),
+                card(Suit::Hearts, Rank::King),
+            ]),
+            UnorderedCards::from_iter([
+                card(Suit::Spades, Rank::Num4),
+                card(Suit::Clubs, Rank::Num2),
+            ]),
+        ];
+        let mut round = Round::new(hands, 1, None);
+        assert_eq!(
+            Err(RuleError::NotYourTurn {
+                expected: 1,
+                actual: 0
+            }),
+            round.play(0, card(Suit::Spades, Rank::Num3))
+        );
+        assert_eq!(
+            Err(RuleError::NotInHand(card(Suit::Spades, Rank::Num3))),
+            round.play(1, card(Suit::Spades, Rank::Num3))
+        );
+        assert_eq!(Ok(None), round.play(1, card(Suit::Spades, Rank::Num4)));
+        assert_eq!(
+            Err(RuleError::MustFollowSuit(Suit::Spades)),
+            round.play(0, card(Suit::Hearts, Rank::King))
+        );
+        assert_eq!(Ok(Some(1)), round.play(0, card(Suit::Spades, Rank::Num3)));
+        assert_eq!(1, round.tricks_won(1));
+        assert_eq!(Some([0, 1]), round.team_tricks());
+    }
+
+    #[test]
+    fn test_round_rejects_unknown_seat() {
+        let hands = vec![UnorderedCards::from_iter([card(Suit::Spades, Rank::Num3)])];
+        let mut round = Round::new(hands, 2, None);
+        assert_eq!(
+            Err(RuleError::InvalidSeat(2)),
+            round.play(2, card(Suit::Spades, Rank::Num3))
+        );
+    }
+
+    #[test]
+    fn test_illegal_play() {
+        let hand = UnorderedCards::from_iter([
+            card(Suit::Spades, Rank::Num3),
+            card(Suit::Hearts, Rank::King),
+        ]);
+        let mut trick = Trick::new();
+        // a variant may refuse a card although no suit was led
+        assert_eq!(
+            RuleError::IllegalCard(card(Suit::Spades, Rank::Num3)),
+            illegal_play(&hand, &trick, card(Suit::Spades, Rank::Num3))
+        );
+        trick.play(0, card(Suit::Hearts, Rank::Num2));
+        assert_eq!(
+            RuleError::MustFollowSuit(Suit::Hearts),
+            illegal_play(&hand, &trick, card(Suit::Spades, Rank::Num3))
+        );
+        assert_eq!(
+            RuleError::IllegalCard(card(Suit::Hearts, Rank::King)),
+            illegal_play(&hand, &trick, card(Suit::Hearts, Rank::King))
+        );
+    }
+
+    #[test]
+    fn test_round_finished() {
+        let hands = vec![
+            UnorderedCards::from_iter([card(Suit::Spades, Rank::Num3)]),
+            UnorderedCards::from_iter([card(Suit::Clubs, Rank::Num2)]),
+        ];
+        let mut round = Round::new(hands, 0, Some(Suit::Clubs));
+        assert_eq!(Ok(None), round.play(0, card(Suit::Spades, Rank::Num3)));
+        assert!(!round.is_finished());
+        assert_eq!(Ok(Some(1)), round.play(1, card(Suit::Clubs, Rank::Num2)));
+        assert!(round.is_finished());
+        assert_eq!(
+            Err(RuleError::RoundFinished),
+            round.play(1, card(Suit::Spades, Rank::Num3))
+        );
+    }
+
+    #[test]
+    fn test_round_draws_from_stock() {
+        let hands = vec![
+            UnorderedCards::from_iter([card(Suit::Spades, Rank::Num3)]),
+            UnorderedCards::from_iter([card(Suit::Spades, Rank::Ace)]),
+        ];
+        let stock = OrderedCards::from_iter([
+            card(Suit::Hearts, Rank::Queen),
+            card(Suit::Clubs, Rank::Num4),
+        ]);
+        let mut round = Round::new(hands, 0, Some(Suit::Hearts))
+            .with_variant(VariantKind::German, None)
+            .with_stock(stock);
+        assert_eq!(Ok(None), round.play(0, card(Suit::Spades, Rank::Num3)));
+        assert_eq!(Ok(Some(1)), round.play(1, card(Suit::Spades, Rank::Ace)));
+        assert!(
+            round
+                .hand(1)
+                .unwrap()
+                .contains(&card(Suit::Hearts, Rank::Queen))
+        );
+        assert!(
+            round
+                .hand(0)
+                .unwrap()
+                .contains(&card(Suit::Clubs, Rank::Num4))
+        );
+        assert_eq!(0, round.stock().len());
+        assert_eq!(None, round.team_tricks());
+        assert!(!round.is_finished());
+    }
+}
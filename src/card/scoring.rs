@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+
+use crate::card::{Card, CardContainer, Rank, Suit, UnorderedCards};
+
+/// Number of teams in a partnership game.
+pub const TEAM_COUNT: u8 = 2;
+
+/// Returns the team of a seat, partners sit opposite each other.
+pub fn team_of_seat(seat: u8) -> u8 {
+    seat % TEAM_COUNT
+}
+
+/// Tricks a team has to take before it scores, every trick over it is worth a point.
+pub const BOOK: u8 = 6;
+/// Points needed to win a game.
+pub const GAME_POINTS: u32 = 5;
+/// Games needed to win the rubber, which is played best of three.
+pub const RUBBER_GAMES: u8 = 2;
+
+const HONOURS: [Rank; 4] = [Rank::Ace, Rank::King, Rank::Queen, Rank::Jack];
+
+/// Values indexed by team.
+pub type TeamValues<T> = [T; TEAM_COUNT as usize];
+
+/// Points of one hand, as sent by the server and computed locally.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HandScore {
+    pub tricks: TeamValues<u8>,
+    pub trick_points: TeamValues<u32>,
+    #[serde(default)]
+    pub honour_points: TeamValues<u32>,
+}
+
+impl HandScore {
+    pub fn new(tricks: TeamValues<u8>, honour_points: TeamValues<u32>) -> Self {
+        Self {
+            tricks,
+            trick_points: tricks.map(trick_points),
+            honour_points,
+        }
+    }
+
+    pub fn points(&self) -> TeamValues<u32> {
+        let mut points = self.trick_points;
+        for (points, honours) in points.iter_mut().zip(self.honour_points) {
+            *points += honours;
+        }
+        points
+    }
+}
+
+pub fn trick_points(tricks: u8) -> u32 {
+    tricks.saturating_sub(BOOK) as u32
+}
+
+/// Honours are the ace, king, queen and jack of trumps, dealt hands are indexed by seat.
+///
+/// A team holding all four scores four points, a team holding three scores two.
+pub fn honour_points(hands: &[UnorderedCards], trump: Suit) -> TeamValues<u32> {
+    let mut held = [0; TEAM_COUNT as usize];
+    for (seat, hand) in hands.iter().enumerate() {
+        let count = HONOURS
+            .iter()
+            .filter(|rank| {
+                hand.contains(&Card {
+                    suit: trump,
+                    rank: **rank,
+                })
+            })
+            .count();
+        held[team_of_seat(seat as u8) as usize] += count;
+    }
+    held.map(|count| match count {
+        4 => 4,
+        3 => 2,
+        _ => 0,
+    })
+}
+
+/// Running totals of a rubber, each game is won by the first team reaching [`GAME_POINTS`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Rubber {
+    pub games: TeamValues<u8>,
+    /// Points in the current game.
+    pub game_points: TeamValues<u32>,
+    pub hands: Vec<HandScore>,
+}
+
+impl Rubber {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the points of a hand, hands after the end of the rubber are ignored.
+    pub fn add_hand(&mut self, hand: HandScore) {
+        if self.is_finished() {
+            return;
+        }
+        for (points, hand_points) in self.game_points.iter_mut().zip(hand.points()) {
+            *points += hand_points;
+        }
+        self.hands.push(hand);
+        let leader = (0..TEAM_COUNT).max_by_key(|team| self.game_points[*team as usize]);
+        if let Some(team) = leader.filter(|team| self.game_points[*team as usize] >= GAME_POINTS) {
+            self.games[team as usize] += 1;
+            self.game_points = [0; TEAM_COUNT as usize];
+        }
+    }
+
+    pub fn winner(&self) -> Option<u8> {
+        (0..TEAM_COUNT).find(|team| self.games[*team as usize] >= RUBBER_GAMES)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.winner().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::card::*;
+
+    fn honours(suit: Suit, ranks: &[Rank]) -> UnorderedCards {
+        ranks
+            .iter()
+            .map(|rank| Card { suit, rank: *rank })
+            .collect()
+    }
+
+    #[test]
+    fn test_team_of_seat() {
+        assert_eq!(
+            vec![0, 1, 0, 1],
+            (0..4).map(team_of_seat).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_trick_points() {
+        assert_eq!(0, trick_points(0));
+        assert_eq!(0, trick_points(6));
+        assert_eq!(1, trick_points(7));
+        assert_eq!(7, trick_points(13));
+    }
+
+    #[test]
+    fn test_hand_score_points() {
+        let score = HandScore::new([9, 4], [0, 2]);
+        assert_eq!([3, 0], score.trick_points);
+        assert_eq!([3, 2], score.points());
+    }
+
+    #[test]
+    fn test_hand_score_deserialize() {
+        let actual: HandScore = serde_json::from_value(json!({
+            "tricks": [7, 6],
+            "trick_points": [1, 0]
+        }))
+        .unwrap();
+        assert_eq!(HandScore::new([7, 6], [0, 0]), actual);
+    }
+
+    #[test]
+    fn test_honour_points() {
+        let hands = vec![
+            honours(Suit::Hearts, &[Rank::Ace, Rank::Jack]),
+            honours(Suit::Hearts, &[Rank::Num2]),
+            honours(Suit::Hearts, &[Rank::King, Rank::Queen]),
+            UnorderedCards::new(),
+        ];
+        assert_eq!([4, 0], honour_points(&hands, Suit::Hearts));
+        assert_eq!([0, 0], honour_points(&hands, Suit::Spades));
+
+        let hands = vec![
+            honours(Suit::Hearts, &[Rank::Ace, Rank::Jack]),
+            honours(Suit::Hearts, &[Rank::Queen]),
+            honours(Suit::Hearts, &[Rank::King]),
+        ];
+        assert_eq!([2, 0], honour_points(&hands, Suit::Hearts));
+
+        let hands = vec![
+            honours(Suit::Hearts, &[Rank::Ace, Rank::Jack]),
+            honours(Suit::Hearts, &[Rank::Queen, Rank::King]),
+        ];
+        assert_eq!([0, 0], honour_points(&hands, Suit::Hearts));
+    }
+
+    #[test]
+    fn test_rubber() {
+        let mut rubber = Rubber::new();
+        rubber.add_hand(HandScore::new([10, 3], [0, 0]));
+        assert_eq!([4, 0], rubber.game_points);
+        rubber.add_hand(HandScore::new([5, 8], [0, 0]));
+        assert_eq!([4, 2], rubber.game_points);
+        rubber.add_hand(HandScore::new([7, 6], [0, 0]));
+        assert_eq!([1, 0], rubber.games);
+        assert_eq!([0, 0], rubber.game_points);
+        assert!(!rubber.is_finished());
+
+        rubber.add_hand(HandScore::new([13, 0], [0, 0]));
+        assert_eq!(Some(0), rubber.winner());
+        rubber.add_hand(HandScore::new([0, 13], [0, 0]));
+        assert_eq!(4, rubber.hands.len());
+    }
+}
@@ -0,0 +1,275 @@
+use std::ops::{BitAnd, BitOr, Sub};
+
+use serde::{Deserialize, Serialize};
+
+use crate::card::{Card, CardContainer, CardEnum, Rank, Suit, UnorderedCards};
+
+/// Cards as bits of a mask, the bit of a card is its index.
+///
+/// Iterates like [`UnorderedCards`]: by suit, then by ascending rank.
+/// Serialized in the same format as [`UnorderedCards`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "UnorderedCards", into = "UnorderedCards")]
+pub struct CardSet {
+    mask: u64,
+}
+
+impl CardSet {
+    const SUIT_MASK: u64 = (1 << Rank::COUNT) - 1;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_mask(mask: u64) -> Self {
+        Self {
+            mask: mask & ((1 << Card::COUNT) - 1),
+        }
+    }
+
+    pub fn mask(&self) -> u64 {
+        self.mask
+    }
+
+    /// All thirteen cards of `suit`.
+    pub fn of_suit(suit: Suit) -> Self {
+        let suit: u8 = suit.into();
+        Self {
+            mask: Self::SUIT_MASK << (suit * Rank::COUNT),
+        }
+    }
+
+    pub fn union(&self, other: &CardSet) -> CardSet {
+        Self {
+            mask: self.mask | other.mask,
+        }
+    }
+
+    pub fn intersection(&self, other: &CardSet) -> CardSet {
+        Self {
+            mask: self.mask & other.mask,
+        }
+    }
+
+    pub fn difference(&self, other: &CardSet) -> CardSet {
+        Self {
+            mask: self.mask & !other.mask,
+        }
+    }
+
+    /// The cards of this set in `suit`.
+    pub fn in_suit(&self, suit: Suit) -> CardSet {
+        self.intersection(&Self::of_suit(suit))
+    }
+
+    pub fn has_suit(&self, suit: Suit) -> bool {
+        !self.in_suit(suit).is_empty()
+    }
+
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter { mask: self.mask }
+    }
+
+    fn bit(card: &Card) -> u64 {
+        let index: u8 = (*card).into();
+        1 << index
+    }
+}
+
+impl CardContainer for CardSet {
+    fn add(&mut self, card: Card) {
+        self.mask |= Self::bit(&card);
+    }
+
+    fn remove(&mut self, card: &Card) -> bool {
+        let present = self.contains(card);
+        self.mask &= !Self::bit(card);
+        present
+    }
+
+    fn contains(&self, card: &Card) -> bool {
+        self.mask & Self::bit(card) != 0
+    }
+
+    fn len(&self) -> u8 {
+        self.mask.count_ones() as u8
+    }
+
+    fn iter(&self) -> impl Iterator<Item = Card> + '_ {
+        CardSet::iter(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.mask == 0
+    }
+
+    fn cards_of_suit(&self, suit: Suit) -> Self {
+        self.in_suit(suit)
+    }
+
+    fn is_void(&self, suit: Suit) -> bool {
+        !self.has_suit(suit)
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(&rhs)
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(&rhs)
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(&rhs)
+    }
+}
+
+/// Yields the lowest card first by clearing one bit at a time.
+#[derive(Debug, Clone)]
+pub struct CardSetIter {
+    mask: u64,
+}
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        let index = self.mask.trailing_zeros() as u8;
+        self.mask &= self.mask - 1;
+        Card::try_from(index).ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<T: IntoIterator<Item = Card>>(iter: T) -> Self {
+        let mut cards = Self::new();
+        cards.extend(iter);
+        cards
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<T: IntoIterator<Item = Card>>(&mut self, iter: T) {
+        for card in iter {
+            self.add(card);
+        }
+    }
+}
+
+impl From<UnorderedCards> for CardSet {
+    fn from(cards: UnorderedCards) -> Self {
+        cards.into_iter().collect()
+    }
+}
+
+impl From<CardSet> for UnorderedCards {
+    fn from(cards: CardSet) -> Self {
+        cards.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn set(notation: &str) -> CardSet {
+        notation.parse::<UnorderedCards>().unwrap().into()
+    }
+
+    #[test]
+    fn test_add_remove_contains() {
+        let mut cards = CardSet::new();
+        let ace: Card = "AH".parse().unwrap();
+        cards.add(ace);
+        cards.add(ace);
+        assert!(cards.contains(&ace));
+        assert_eq!(1, cards.len());
+        assert_eq!(1 << 38, cards.mask());
+        assert!(cards.remove(&ace));
+        assert!(!cards.remove(&ace));
+        assert!(cards.is_empty());
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a = set("S:AK H:2");
+        let b = set("S:K H:3");
+        assert_eq!(set("S:AK H:32"), a | b);
+        assert_eq!(set("S:K"), a & b);
+        assert_eq!(set("S:A H:2"), a - b);
+        assert_eq!(a.union(&b), a | b);
+    }
+
+    #[test]
+    fn test_suits() {
+        let cards = set("S:AK D:T C:2");
+        assert_eq!(13, CardSet::of_suit(Suit::Hearts).len());
+        assert_eq!(set("S:AK"), cards.in_suit(Suit::Spades));
+        assert!(cards.has_suit(Suit::Diamonds));
+        assert!(!cards.has_suit(Suit::Hearts));
+        assert_eq!(CardSet::from_mask(u64::MAX), CardSet::all());
+        assert_eq!(52, CardSet::all().len());
+    }
+
+    #[test]
+    fn test_iterates_like_unordered_cards() {
+        let cards = set("S:AK H:T92 C:J8");
+        let expected: Vec<Card> = UnorderedCards::from(cards).into_iter().collect();
+        assert_eq!(expected, cards.iter().collect::<Vec<_>>());
+        assert_eq!(7, cards.iter().len());
+    }
+
+    #[test]
+    fn test_serde_matches_unordered_cards() {
+        let cards = set("H:42");
+        let expected = json!({"cards": [
+            {"suit": "hearts", "rank": '2'},
+            {"suit": "hearts", "rank": '4'}
+        ]});
+        assert_eq!(expected, serde_json::to_value(cards).unwrap());
+        assert_eq!(cards, serde_json::from_value(expected).unwrap());
+    }
+}
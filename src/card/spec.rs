@@ -0,0 +1,327 @@
+use std::cmp::Ordering;
+
+use crate::card::{Card, CardEnum, Rank, Suit};
+
+/// Which rank is lowest, all other ranks keep their natural order.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RankOrder {
+    #[default]
+    AceHigh,
+    AceLow,
+}
+
+/// A card of a deck that may contain jokers, which are not part of any suit.
+///
+/// Jokers are only counted and indexed by [`DeckSpec`], a [`Card`] can not be a joker
+/// so they are never dealt, held or ranked in a trick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeckCard {
+    Card(Card),
+    /// Jokers are numbered from zero.
+    Joker(u8),
+}
+
+impl DeckCard {
+    pub fn card(self) -> Option<Card> {
+        match self {
+            DeckCard::Card(card) => Some(card),
+            DeckCard::Joker(_) => None,
+        }
+    }
+}
+
+impl From<Card> for DeckCard {
+    fn from(card: Card) -> Self {
+        Self::Card(card)
+    }
+}
+
+/// Cards a deck is made of and how their ranks compare.
+///
+/// Cards are indexed by suit, then by rank from lowest to highest, followed by the jokers.
+/// For [`DeckSpec::STANDARD`] this is the index of `Card as u8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeckSpec {
+    /// Bit per included suit, by suit index.
+    suits: u8,
+    /// Bit per included rank, by rank index.
+    ranks: u16,
+    jokers: u8,
+    order: RankOrder,
+}
+
+impl DeckSpec {
+    /// 52 cards, ace high.
+    pub const STANDARD: DeckSpec = DeckSpec {
+        suits: (1 << Suit::COUNT) - 1,
+        ranks: (1 << Rank::COUNT) - 1,
+        jokers: 0,
+        order: RankOrder::AceHigh,
+    };
+
+    /// 32 cards from seven to ace.
+    pub const PIQUET: DeckSpec = DeckSpec {
+        ranks: DeckSpec::STANDARD.ranks & !((1 << Rank::Num7 as u16) - 1),
+        ..DeckSpec::STANDARD
+    };
+
+    /// Most jokers a deck can count, so that every index fits a `u8`.
+    pub const MAX_JOKERS: u8 = u8::MAX - Card::COUNT;
+
+    pub fn with_suits(mut self, suits: impl IntoIterator<Item = Suit>) -> Self {
+        self.suits = suits
+            .into_iter()
+            .fold(0, |mask, suit| mask | 1 << suit as u8);
+        self
+    }
+
+    pub fn with_ranks(mut self, ranks: impl IntoIterator<Item = Rank>) -> Self {
+        self.ranks = ranks
+            .into_iter()
+            .fold(0, |mask, rank| mask | 1 << rank as u16);
+        self
+    }
+
+    /// Counts `jokers` after the cards, at most [`DeckSpec::MAX_JOKERS`].
+    ///
+    /// They are never dealt, see [`DeckCard`].
+    pub fn with_jokers(mut self, jokers: u8) -> Self {
+        self.jokers = jokers.min(Self::MAX_JOKERS);
+        self
+    }
+
+    pub fn with_order(mut self, order: RankOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn jokers(&self) -> u8 {
+        self.jokers
+    }
+
+    pub fn suits(&self) -> impl Iterator<Item = Suit> + '_ {
+        Suit::all().filter(|suit| self.suits & (1 << *suit as u8) != 0)
+    }
+
+    /// Included ranks from lowest to highest.
+    pub fn ranks(&self) -> impl Iterator<Item = Rank> + '_ {
+        let ace_low = self.order == RankOrder::AceLow;
+        ace_low
+            .then_some(Rank::Ace)
+            .into_iter()
+            .chain(Rank::all().filter(move |rank| !ace_low || *rank != Rank::Ace))
+            .filter(|rank| self.ranks & (1 << *rank as u16) != 0)
+    }
+
+    /// All cards without the jokers, in the order of their indices.
+    pub fn cards(&self) -> impl Iterator<Item = Card> + '_ {
+        self.suits()
+            .flat_map(|suit| self.ranks().map(move |rank| Card { suit, rank }))
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.suits & (1 << card.suit as u8) != 0 && self.ranks & (1 << card.rank as u16) != 0
+    }
+
+    /// Number of cards including the jokers.
+    pub fn len(&self) -> u8 {
+        self.suited_len() + self.jokers
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn suited_len(&self) -> u8 {
+        self.suits.count_ones() as u8 * self.ranks.count_ones() as u8
+    }
+
+    pub fn index(&self, card: &DeckCard) -> Option<u8> {
+        match card {
+            DeckCard::Card(card) => {
+                if !self.contains(card) {
+                    return None;
+                }
+                let suit = self.suits().take_while(|suit| *suit != card.suit).count() as u8;
+                let rank = self.ranks().take_while(|rank| *rank != card.rank).count() as u8;
+                Some(suit * self.ranks.count_ones() as u8 + rank)
+            }
+            DeckCard::Joker(joker) => (*joker < self.jokers).then(|| self.suited_len() + joker),
+        }
+    }
+
+    pub fn get(&self, index: u8) -> Option<DeckCard> {
+        let suited = self.suited_len();
+        if index >= suited {
+            return (index < self.len()).then_some(DeckCard::Joker(index - suited));
+        }
+        let per_suit = self.ranks.count_ones() as u8;
+        let suit = self.suits().nth((index / per_suit) as usize)?;
+        let rank = self.ranks().nth((index % per_suit) as usize)?;
+        Some(DeckCard::Card(Card { suit, rank }))
+    }
+
+    /// Position of `rank` in the rank order, higher is stronger.
+    pub fn strength(&self, rank: Rank) -> u8 {
+        match (self.order, rank) {
+            (RankOrder::AceLow, Rank::Ace) => 0,
+            (RankOrder::AceLow, rank) => rank as u8 + 1,
+            (RankOrder::AceHigh, rank) => rank as u8,
+        }
+    }
+
+    pub fn compare(&self, rank: Rank, other: Rank) -> Ordering {
+        self.strength(rank).cmp(&self.strength(other))
+    }
+
+    /// Orders cards like their indices, by suit and then by rank strength.
+    pub fn compare_cards(&self, card: &Card, other: &Card) -> Ordering {
+        card.suit
+            .cmp(&other.suit)
+            .then_with(|| self.compare(card.rank, other.rank))
+    }
+
+    /// Returns whether `card` beats `other` in a trick led with `led`.
+    pub fn beats(&self, card: Card, other: Card, led: Suit, trump: Option<Suit>) -> bool {
+        let rank = |card: Card| {
+            if Some(card.suit) == trump {
+                Some((2, self.strength(card.rank)))
+            } else if card.suit == led {
+                Some((1, self.strength(card.rank)))
+            } else {
+                None
+            }
+        };
+        rank(card) > rank(other)
+    }
+}
+
+impl Default for DeckSpec {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::card::{CardContainer, UnorderedCards};
+
+    fn card(short: &str) -> DeckCard {
+        DeckCard::Card(short.parse().unwrap())
+    }
+
+    #[test]
+    fn test_standard_matches_card_index() {
+        let spec = DeckSpec::default();
+        assert_eq!(52, spec.len());
+        let cards: Vec<Card> = spec.cards().collect();
+        assert_eq!(Card::all().collect::<Vec<_>>(), cards);
+        for card in Card::all() {
+            let index: u8 = card.into();
+            assert_eq!(Some(index), spec.index(&card.into()));
+            assert_eq!(Some(DeckCard::Card(card)), spec.get(index));
+        }
+        assert_eq!(None, spec.get(52));
+    }
+
+    #[test]
+    fn test_piquet() {
+        let spec = DeckSpec::PIQUET;
+        assert_eq!(32, spec.len());
+        assert_eq!(Some(Rank::Num7), spec.ranks().next());
+        assert!(!spec.contains(&"6H".parse().unwrap()));
+        assert_eq!(None, spec.index(&card("6H")));
+        assert_eq!(Some(8), spec.index(&card("7D")));
+        assert_eq!(Some(card("AS")), spec.get(31));
+        assert_eq!(None, spec.get(32));
+    }
+
+    #[test]
+    fn test_jokers_follow_the_cards() {
+        let spec = DeckSpec::STANDARD.with_jokers(2);
+        assert_eq!(54, spec.len());
+        assert_eq!(2, spec.jokers());
+        assert_eq!(Some(53), spec.index(&DeckCard::Joker(1)));
+        assert_eq!(None, spec.index(&DeckCard::Joker(2)));
+        assert_eq!(Some(DeckCard::Joker(0)), spec.get(52));
+        assert_eq!(None, spec.get(54));
+        assert_eq!(52, spec.cards().count());
+    }
+
+    #[test]
+    fn test_jokers_are_bounded() {
+        let spec = DeckSpec::STANDARD.with_jokers(u8::MAX);
+        assert_eq!(DeckSpec::MAX_JOKERS, spec.jokers());
+        assert_eq!(u8::MAX, spec.len());
+        assert_eq!(
+            Some(254),
+            spec.index(&DeckCard::Joker(DeckSpec::MAX_JOKERS - 1))
+        );
+        assert_eq!(None, spec.index(&DeckCard::Joker(DeckSpec::MAX_JOKERS)));
+        assert_eq!(Some(DeckCard::Joker(202)), spec.get(254));
+        assert_eq!(None, spec.get(u8::MAX));
+    }
+
+    #[test]
+    fn test_custom_suits_and_ranks() {
+        let spec = DeckSpec::STANDARD
+            .with_suits([Suit::Hearts, Suit::Spades])
+            .with_ranks([Rank::Ace, Rank::King]);
+        assert_eq!(4, spec.len());
+        let cards: Vec<String> = spec.cards().map(|card| card.short()).collect();
+        assert_eq!(vec!["KH", "AH", "KS", "AS"], cards);
+        assert_eq!(Some(2), spec.index(&card("KS")));
+        assert_eq!(None, spec.index(&card("KD")));
+        assert_eq!(Some(card("AH")), spec.get(1));
+    }
+
+    #[test]
+    fn test_thirty_six_cards() {
+        let spec = DeckSpec::STANDARD.with_ranks(Rank::all().filter(|rank| *rank >= Rank::Num6));
+        assert_eq!(36, spec.len());
+        assert_eq!(Some(Rank::Num6), spec.ranks().next());
+        assert_eq!(Some(card("6D")), spec.get(9));
+    }
+
+    #[test]
+    fn test_no_suits() {
+        let spec = DeckSpec::STANDARD.with_suits([]).with_jokers(2);
+        assert_eq!(2, spec.len());
+        assert_eq!(0, spec.cards().count());
+        assert_eq!(Some(DeckCard::Joker(0)), spec.get(0));
+    }
+
+    #[test]
+    fn test_ace_low() {
+        let spec = DeckSpec::STANDARD.with_order(RankOrder::AceLow);
+        assert_eq!(Ordering::Less, spec.compare(Rank::Ace, Rank::Num2));
+        assert_eq!(Some(Rank::Ace), spec.ranks().next());
+        assert_eq!(Some(card("AC")), spec.get(0));
+        assert_eq!(Some(13), spec.index(&card("AD")));
+        let cards: Vec<Card> = Card::all_in(&spec).collect();
+        assert_eq!(Some(0), spec.index(&cards[0].into()));
+        assert!(cards.is_sorted_by(|a, b| spec.compare_cards(a, b).is_lt()));
+        let ace = "AH".parse().unwrap();
+        let two = "2H".parse().unwrap();
+        assert!(spec.beats(two, ace, Suit::Hearts, None));
+        let hand: UnorderedCards = "S:- H:A32 D:- C:-".parse().unwrap();
+        assert_eq!(
+            Some("3H".parse().unwrap()),
+            hand.highest_in_suit_of(Suit::Hearts, &spec)
+        );
+        assert_eq!(Some(ace), hand.lowest_in_suit_of(Suit::Hearts, &spec));
+    }
+
+    #[test]
+    fn test_piquet_ace_low() {
+        let spec = DeckSpec::PIQUET.with_order(RankOrder::AceLow);
+        let ranks: Vec<Rank> = spec.ranks().collect();
+        assert_eq!(Rank::Ace, ranks[0]);
+        assert_eq!(Rank::Num7, ranks[1]);
+        assert_eq!(8, ranks.len());
+        assert_eq!(Some(card("KH")), spec.get(23));
+    }
+}
@@ -0,0 +1,661 @@
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+use crate::card::{
+    Card, CardEnum, Deck, DeckError, DeckSpec, OrderedCards, Rank, RankOrder, SortMode, Suit,
+    Trick, UnorderedCards, team_of_seat,
+};
+
+/// Whist variants a room can be played with.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum VariantKind {
+    #[default]
+    Classic,
+    German,
+    OhHell,
+    KnockOut,
+    Solo,
+    Bid,
+}
+
+impl VariantKind {
+    pub const ALL: [VariantKind; 6] = [
+        VariantKind::Classic,
+        VariantKind::German,
+        VariantKind::OhHell,
+        VariantKind::KnockOut,
+        VariantKind::Solo,
+        VariantKind::Bid,
+    ];
+
+    pub fn rules(&self) -> &'static dyn Variant {
+        match self {
+            VariantKind::Classic => &ClassicWhist,
+            VariantKind::German => &GermanWhist,
+            VariantKind::OhHell => &OhHell,
+            VariantKind::KnockOut => &KnockOutWhist,
+            VariantKind::Solo => &SoloWhist,
+            VariantKind::Bid => &BidWhist,
+        }
+    }
+}
+
+impl Display for VariantKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rules().name())
+    }
+}
+
+/// A bid or contract made before the cards are played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "bid", rename_all = "snake_case")]
+pub enum Bid {
+    Pass,
+    /// Exact number of tricks a player expects to take, Oh Hell.
+    Tricks {
+        tricks: u8,
+    },
+    /// Solo Whist: looking for a partner to take eight tricks with.
+    Proposal,
+    /// Solo Whist: partners with the proposer.
+    Accept,
+    /// Solo Whist: five tricks alone with the turned up trump.
+    Solo,
+    /// Solo Whist: no tricks at all, without trumps.
+    Misere,
+    /// Solo Whist: nine tricks alone with a trump of choice.
+    Abundance {
+        trump: Suit,
+    },
+    /// Solo Whist: misère with the hand laid open after the first trick.
+    OpenMisere,
+    /// Solo Whist: all thirteen tricks without trumps.
+    AbundanceDeclared,
+    /// Bid Whist: tricks over six, low cards win when `downtown`.
+    Books {
+        books: u8,
+        trump: Option<Suit>,
+        downtown: bool,
+    },
+}
+
+impl Bid {
+    /// Order of the Solo Whist contracts, higher contracts overcall lower ones.
+    fn solo_rank(&self) -> Option<u8> {
+        match self {
+            Bid::Proposal => Some(0),
+            Bid::Accept => Some(1),
+            Bid::Solo => Some(2),
+            Bid::Misere => Some(3),
+            Bid::Abundance { .. } => Some(4),
+            Bid::OpenMisere => Some(5),
+            Bid::AbundanceDeclared => Some(6),
+            _ => None,
+        }
+    }
+
+    /// Bid Whist ranking, no trump outranks a suit bid with the same number of books.
+    fn books_rank(&self) -> Option<(u8, bool)> {
+        match self {
+            Bid::Books { books, trump, .. } => Some((*books, trump.is_none())),
+            _ => None,
+        }
+    }
+}
+
+/// Rules of one whist variant, the rest of the game only talks to this trait.
+pub trait Variant: Sync {
+    fn name(&self) -> &'static str;
+
+    /// Number of players the variant can be played with.
+    fn players(&self) -> RangeInclusive<u8>;
+
+    /// Cards dealt to each player in `round`, counted from zero.
+    ///
+    /// Returns `None` once the game is over.
+    fn hand_size(&self, round: u32, players: u8) -> Option<u8>;
+
+    /// Team of the player in `seat`, `None` when everyone plays for themselves.
+    fn team(&self, seat: u8) -> Option<u8> {
+        Some(team_of_seat(seat))
+    }
+
+    /// Whether players draw from the stock after each trick, as in German Whist.
+    fn draws_from_stock(&self) -> bool {
+        false
+    }
+
+    /// Bids the next player may make, given the bids made so far in this round.
+    ///
+    /// Empty if the variant has no auction.
+    fn bids(&self, _hand_size: u8, _players: u8, _previous: &[Bid]) -> Vec<Bid> {
+        Vec::new()
+    }
+
+    /// Trump of a round, from the winning contract or else the turned up card.
+    fn trump(&self, contract: Option<&Bid>, turned_up: Option<Card>) -> Option<Suit> {
+        let _ = contract;
+        turned_up.map(|card| card.suit)
+    }
+
+    /// Cards the variant is played with and how their ranks compare.
+    fn deck(&self) -> DeckSpec {
+        DeckSpec::STANDARD
+    }
+
+    /// Shuffles the deck of the variant with `seed` and deals `hand_size` cards to each player.
+    ///
+    /// The cards left over stay in the deck, to turn up trumps or to draw from.
+    fn deal(
+        &self,
+        hand_size: u8,
+        players: u8,
+        seed: u64,
+    ) -> Result<(Vec<UnorderedCards>, Deck), DeckError> {
+        let mut deck = Deck::from_spec(&self.deck());
+        deck.shuffle(seed);
+        let hands = deck.deal(players as usize, hand_size as usize)?;
+        Ok((hands, deck))
+    }
+
+    /// Cards of `hand` which may be played onto `trick`, by default following suit.
+    fn legal_plays(&self, hand: &UnorderedCards, trick: &Trick) -> UnorderedCards {
+        hand.playable(trick.led_suit())
+    }
+
+    fn trick_winner(
+        &self,
+        trick: &Trick,
+        trump: Option<Suit>,
+        contract: Option<&Bid>,
+    ) -> Option<u8> {
+        let _ = contract;
+        let deck = self.deck();
+        trick.winner_by(|card, other, led| deck.beats(card, other, led, trump))
+    }
+
+    /// Sorts a hand for display, see [`SortMode::Trump`].
+    fn sort_hand(&self, hand: &OrderedCards, trump: Option<Suit>) -> OrderedCards {
+        let mut hand = hand.clone();
+        hand.sort_in(SortMode::Trump(trump), &self.deck());
+        hand
+    }
+}
+
+/// Number of cards each player gets when the whole `deck` is dealt, jokers are not dealt.
+fn full_deal(deck: DeckSpec, players: u8) -> u8 {
+    (deck.len() - deck.jokers()) / players.max(1)
+}
+
+#[derive(Debug)]
+pub struct ClassicWhist;
+
+impl Variant for ClassicWhist {
+    fn name(&self) -> &'static str {
+        "Classic Whist"
+    }
+
+    fn players(&self) -> RangeInclusive<u8> {
+        4..=4
+    }
+
+    fn hand_size(&self, _round: u32, players: u8) -> Option<u8> {
+        Some(full_deal(self.deck(), players))
+    }
+}
+
+/// Two players, the second half of the deck is drawn one card per trick.
+#[derive(Debug)]
+pub struct GermanWhist;
+
+impl Variant for GermanWhist {
+    fn name(&self) -> &'static str {
+        "German Whist"
+    }
+
+    fn players(&self) -> RangeInclusive<u8> {
+        2..=2
+    }
+
+    fn team(&self, _seat: u8) -> Option<u8> {
+        None
+    }
+
+    fn hand_size(&self, _round: u32, _players: u8) -> Option<u8> {
+        Some(Rank::COUNT)
+    }
+
+    fn draws_from_stock(&self) -> bool {
+        true
+    }
+}
+
+/// Hand sizes go up by one each round and back down, everyone bids their exact tricks.
+#[derive(Debug)]
+pub struct OhHell;
+
+impl OhHell {
+    const MAX_HAND_SIZE: u8 = 10;
+
+    fn max_hand_size(&self, players: u8) -> u8 {
+        // one card stays in the deck to turn up trumps
+        ((self.deck().len() - 1) / players.max(1)).min(Self::MAX_HAND_SIZE)
+    }
+}
+
+impl Variant for OhHell {
+    fn name(&self) -> &'static str {
+        "Oh Hell"
+    }
+
+    fn players(&self) -> RangeInclusive<u8> {
+        3..=7
+    }
+
+    fn team(&self, _seat: u8) -> Option<u8> {
+        None
+    }
+
+    fn hand_size(&self, round: u32, players: u8) -> Option<u8> {
+        let max = self.max_hand_size(players) as u32;
+        if round < max {
+            Some(round as u8 + 1)
+        } else if round < 2 * max - 1 {
+            Some((2 * max - 1 - round) as u8)
+        } else {
+            None
+        }
+    }
+
+    /// The dealer bids last and may not make the total bids equal the number of tricks.
+    fn bids(&self, hand_size: u8, players: u8, previous: &[Bid]) -> Vec<Bid> {
+        let forbidden = if previous.len() + 1 == players as usize {
+            let total: u8 = previous
+                .iter()
+                .map(|bid| match bid {
+                    Bid::Tricks { tricks } => *tricks,
+                    _ => 0,
+                })
+                .sum();
+            hand_size.checked_sub(total)
+        } else {
+            None
+        };
+        (0..=hand_size)
+            .filter(|tricks| Some(*tricks) != forbidden)
+            .map(|tricks| Bid::Tricks { tricks })
+            .collect()
+    }
+}
+
+/// Seven cards in the first round and one less each round after.
+#[derive(Debug)]
+pub struct KnockOutWhist;
+
+impl KnockOutWhist {
+    const FIRST_HAND_SIZE: u8 = 7;
+}
+
+impl Variant for KnockOutWhist {
+    fn name(&self) -> &'static str {
+        "Knock-out Whist"
+    }
+
+    fn players(&self) -> RangeInclusive<u8> {
+        2..=7
+    }
+
+    fn team(&self, _seat: u8) -> Option<u8> {
+        None
+    }
+
+    fn hand_size(&self, round: u32, _players: u8) -> Option<u8> {
+        u8::try_from(round)
+            .ok()
+            .and_then(|round| Self::FIRST_HAND_SIZE.checked_sub(round))
+            .filter(|size| *size > 0)
+    }
+}
+
+#[derive(Debug)]
+pub struct SoloWhist;
+
+impl Variant for SoloWhist {
+    fn name(&self) -> &'static str {
+        "Solo Whist"
+    }
+
+    fn players(&self) -> RangeInclusive<u8> {
+        4..=4
+    }
+
+    fn team(&self, _seat: u8) -> Option<u8> {
+        None
+    }
+
+    fn hand_size(&self, _round: u32, players: u8) -> Option<u8> {
+        Some(full_deal(self.deck(), players))
+    }
+
+    /// Every bid has to overcall the highest one so far, a proposal can only be accepted.
+    fn bids(&self, _hand_size: u8, _players: u8, previous: &[Bid]) -> Vec<Bid> {
+        let highest = previous.iter().filter_map(Bid::solo_rank).max();
+        let mut contracts = vec![Bid::Proposal, Bid::Accept, Bid::Solo, Bid::Misere];
+        contracts.extend(Suit::all().map(|trump| Bid::Abundance { trump }));
+        contracts.extend([Bid::OpenMisere, Bid::AbundanceDeclared]);
+        let mut bids = vec![Bid::Pass];
+        bids.extend(contracts.into_iter().filter(|bid| {
+            let rank = bid.solo_rank();
+            match bid {
+                Bid::Accept => highest == Some(0),
+                _ => rank > highest,
+            }
+        }));
+        bids
+    }
+
+    fn trump(&self, contract: Option<&Bid>, turned_up: Option<Card>) -> Option<Suit> {
+        match contract {
+            Some(Bid::Misere | Bid::OpenMisere | Bid::AbundanceDeclared) => None,
+            Some(Bid::Abundance { trump }) => Some(*trump),
+            _ => ClassicWhist.trump(None, turned_up),
+        }
+    }
+}
+
+/// Partners bid books over six, the winner names trumps or plays downtown where low cards win.
+#[derive(Debug)]
+pub struct BidWhist;
+
+impl BidWhist {
+    /// Three books, nine tricks, is the lowest contract.
+    const MIN_BOOKS: u8 = 3;
+    const MAX_BOOKS: u8 = 7;
+    const HAND_SIZE: u8 = 12;
+}
+
+impl Variant for BidWhist {
+    fn name(&self) -> &'static str {
+        "Bid Whist"
+    }
+
+    fn players(&self) -> RangeInclusive<u8> {
+        4..=4
+    }
+
+    /// Twelve cards each, the four left over are not played as there is no kitty.
+    fn hand_size(&self, _round: u32, _players: u8) -> Option<u8> {
+        Some(Self::HAND_SIZE)
+    }
+
+    fn bids(&self, _hand_size: u8, _players: u8, previous: &[Bid]) -> Vec<Bid> {
+        let highest = previous.iter().filter_map(Bid::books_rank).max();
+        let mut bids = vec![Bid::Pass];
+        for books in Self::MIN_BOOKS..=Self::MAX_BOOKS {
+            for trump in std::iter::once(None).chain(Suit::all().map(Some)) {
+                for downtown in [false, true] {
+                    let bid = Bid::Books {
+                        books,
+                        trump,
+                        downtown,
+                    };
+                    if bid.books_rank() > highest {
+                        bids.push(bid);
+                    }
+                }
+            }
+        }
+        bids
+    }
+
+    fn trump(&self, contract: Option<&Bid>, _turned_up: Option<Card>) -> Option<Suit> {
+        match contract {
+            Some(Bid::Books { trump, .. }) => *trump,
+            _ => None,
+        }
+    }
+
+    fn trick_winner(
+        &self,
+        trick: &Trick,
+        trump: Option<Suit>,
+        contract: Option<&Bid>,
+    ) -> Option<u8> {
+        match contract {
+            // low cards win downtown, reversing ace low keeps the ace on top followed by the two
+            Some(Bid::Books { downtown: true, .. }) => {
+                let deck = self.deck().with_order(RankOrder::AceLow);
+                trick.winner_by(|card, other, led| {
+                    let rank = |card: Card| {
+                        let trump = Some(card.suit) == trump;
+                        (trump || card.suit == led)
+                            .then_some((trump, Reverse(deck.strength(card.rank))))
+                    };
+                    rank(card) > rank(other)
+                })
+            }
+            _ => {
+                let deck = self.deck();
+                trick.winner_by(|card, other, led| deck.beats(card, other, led, trump))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::full_deal;
+    use crate::card::*;
+
+    fn card(suit: Suit, rank: Rank) -> Card {
+        Card { suit, rank }
+    }
+
+    #[test]
+    fn test_variant_kind_serialize() {
+        assert_eq!(
+            json!("oh_hell"),
+            serde_json::to_value(VariantKind::OhHell).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_every_variant_deals_within_the_deck() {
+        for kind in VariantKind::ALL {
+            let rules = kind.rules();
+            for players in rules.players() {
+                let size = rules.hand_size(0, players).unwrap();
+                assert!(
+                    size as u32 * players as u32 <= rules.deck().len() as u32,
+                    "{kind} {players}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_variant_deals_its_first_hand() {
+        for kind in VariantKind::ALL {
+            let rules = kind.rules();
+            for players in rules.players() {
+                let size = rules.hand_size(0, players).unwrap();
+                let (hands, _) = rules.deal(size, players, 3).unwrap();
+                assert_eq!(players as usize, hands.len(), "{kind} {players}");
+                assert!(hands.iter().all(|hand| hand.len() == size));
+            }
+        }
+    }
+
+    #[test]
+    fn test_full_deal_uses_the_whole_deck() {
+        let deck = DeckSpec::PIQUET;
+        let mut cards = Deck::from_spec(&deck);
+        assert!(cards.deal(4, full_deal(deck, 4) as usize).is_ok());
+        assert_eq!(None, cards.draw());
+        assert_eq!(8, full_deal(deck, 4));
+        assert_eq!(13, full_deal(DeckSpec::STANDARD, 4));
+    }
+
+    #[test]
+    fn test_hands_are_sorted_in_the_deck_order() {
+        let hand: OrderedCards = "S:- H:AK2 D:- C:-".parse().unwrap();
+        let sorted = ClassicWhist.sort_hand(&hand, None);
+        let cards: Vec<String> = sorted.iter().map(|card| card.short()).collect();
+        assert_eq!(vec!["AH", "KH", "2H"], cards);
+    }
+
+    #[test]
+    fn test_oh_hell_hand_sizes() {
+        let sizes: Vec<u8> = (0..)
+            .map_while(|round| OhHell.hand_size(round, 7))
+            .collect();
+        assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 6, 5, 4, 3, 2, 1], sizes);
+    }
+
+    #[test]
+    fn test_oh_hell_dealer_may_not_match_tricks() {
+        let previous = [Bid::Tricks { tricks: 1 }, Bid::Tricks { tricks: 0 }];
+        let bids = OhHell.bids(2, 3, &previous);
+        assert_eq!(
+            vec![Bid::Tricks { tricks: 0 }, Bid::Tricks { tricks: 2 }],
+            bids
+        );
+        assert_eq!(3, OhHell.bids(2, 3, &previous[..1]).len());
+    }
+
+    #[test]
+    fn test_knock_out_hand_sizes() {
+        let sizes: Vec<u8> = (0..)
+            .map_while(|round| KnockOutWhist.hand_size(round, 3))
+            .collect();
+        assert_eq!(vec![7, 6, 5, 4, 3, 2, 1], sizes);
+    }
+
+    #[test]
+    fn test_solo_bids_overcall() {
+        assert!(SoloWhist.bids(13, 4, &[]).contains(&Bid::Proposal));
+        assert!(!SoloWhist.bids(13, 4, &[]).contains(&Bid::Accept));
+        assert_eq!(
+            vec![Bid::Pass, Bid::OpenMisere, Bid::AbundanceDeclared],
+            SoloWhist.bids(
+                13,
+                4,
+                &[
+                    Bid::Pass,
+                    Bid::Abundance {
+                        trump: Suit::Spades
+                    }
+                ]
+            )
+        );
+        let after_proposal = SoloWhist.bids(13, 4, &[Bid::Proposal]);
+        assert_eq!(Some(&Bid::Accept), after_proposal.get(1));
+    }
+
+    #[test]
+    fn test_solo_trump() {
+        let turned_up = Some(card(Suit::Clubs, Rank::Num2));
+        assert_eq!(None, SoloWhist.trump(Some(&Bid::Misere), turned_up));
+        assert_eq!(
+            Some(Suit::Hearts),
+            SoloWhist.trump(
+                Some(&Bid::Abundance {
+                    trump: Suit::Hearts
+                }),
+                turned_up
+            )
+        );
+        assert_eq!(
+            Some(Suit::Clubs),
+            SoloWhist.trump(Some(&Bid::Solo), turned_up)
+        );
+    }
+
+    #[test]
+    fn test_bid_whist_opens_at_three_books() {
+        let bids = BidWhist.bids(12, 4, &[]);
+        assert_eq!(
+            Some(&Bid::Books {
+                books: 3,
+                trump: None,
+                downtown: false,
+            }),
+            bids.iter().find(|bid| **bid != Bid::Pass)
+        );
+        assert!(bids.iter().all(|bid| match bid {
+            Bid::Books { books, .. } => *books >= 3,
+            _ => true,
+        }));
+    }
+
+    #[test]
+    fn test_bid_whist_no_trump_overcalls_suit() {
+        let previous = [Bid::Books {
+            books: 4,
+            trump: Some(Suit::Hearts),
+            downtown: false,
+        }];
+        let bids = BidWhist.bids(12, 4, &previous);
+        assert!(bids.contains(&Bid::Books {
+            books: 4,
+            trump: None,
+            downtown: true,
+        }));
+        assert!(!bids.contains(&Bid::Books {
+            books: 4,
+            trump: Some(Suit::Spades),
+            downtown: false,
+        }));
+    }
+
+    #[test]
+    fn test_bid_whist_downtown_winner() {
+        let mut trick = Trick::new();
+        trick.play(0, card(Suit::Hearts, Rank::King));
+        trick.play(1, card(Suit::Hearts, Rank::Num2));
+        trick.play(2, card(Suit::Hearts, Rank::Ace));
+        trick.play(3, card(Suit::Clubs, Rank::Num3));
+        let downtown = Bid::Books {
+            books: 3,
+            trump: None,
+            downtown: true,
+        };
+        assert_eq!(
+            Some(2),
+            BidWhist.trick_winner(&trick, None, Some(&downtown))
+        );
+
+        let mut trick = Trick::new();
+        trick.play(0, card(Suit::Hearts, Rank::King));
+        trick.play(1, card(Suit::Hearts, Rank::Num2));
+        assert_eq!(
+            Some(1),
+            BidWhist.trick_winner(&trick, None, Some(&downtown))
+        );
+        assert_eq!(Some(0), BidWhist.trick_winner(&trick, None, None));
+    }
+
+    #[test]
+    fn test_sort_hand_trumps_right() {
+        let hand = OrderedCards::from_iter([
+            card(Suit::Clubs, Rank::Num2),
+            card(Suit::Spades, Rank::Num3),
+            card(Suit::Clubs, Rank::Ace),
+        ]);
+        let expected = OrderedCards::from_iter([
+            card(Suit::Clubs, Rank::Ace),
+            card(Suit::Clubs, Rank::Num2),
+            card(Suit::Spades, Rank::Num3),
+        ]);
+        assert_eq!(expected, ClassicWhist.sort_hand(&hand, Some(Suit::Spades)));
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bevy_egui::{EguiContexts, egui};
 
+use crate::deep_link::PendingInvite;
 use crate::network::{ConnectResult, NetworkCommand};
 use crate::{GameState, MySystemSets};
 
@@ -61,9 +62,19 @@ impl Default for UiState {
     }
 }
 
-fn add_ui_state(mut commands: Commands) {
+fn add_ui_state(
+    mut commands: Commands,
+    invite: Option<Res<PendingInvite>>,
+    mut event_writer: EventWriter<NetworkCommand>,
+) {
     info!("starting ConnectMenu");
-    commands.init_resource::<UiState>();
+    let mut ui_state = UiState::default();
+    if let Some(invite) = invite {
+        ui_state.connect_url.clone_from(&invite.0.server_url);
+        ui_state.connect_status = ConnectStatus::Connecting;
+        event_writer.send(NetworkCommand::Connect(ui_state.connect_url.to_owned()));
+    }
+    commands.insert_resource(ui_state);
 }
 
 fn remove_ui_state(mut commands: Commands) {
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use reqwest::Url;
+
+#[cfg(not(target_family = "wasm"))]
+use crate::network::INVITE_SCHEME;
+use crate::network::Invite;
+
+/// Command line flag carrying an invite link on native platforms.
+#[cfg(not(target_family = "wasm"))]
+const INVITE_FLAG: &str = "--invite";
+
+/// Reads an invite link given on startup so the menus can join its room on their own.
+pub struct DeepLinkPlugin;
+
+impl Plugin for DeepLinkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, read_invite);
+    }
+}
+
+/// Invite which has not been used yet, the room menu consumes it on its first reconnect.
+#[derive(Resource, Debug)]
+pub struct PendingInvite(pub Invite);
+
+fn read_invite(mut commands: Commands) {
+    let Some(link) = startup_link() else {
+        return;
+    };
+    match Invite::from_link(&link) {
+        Some(Ok(invite)) => {
+            info!("joining room {} from invite link", invite.room_id);
+            commands.insert_resource(PendingInvite(invite));
+        }
+        Some(Err(e)) => warn!("ignoring invalid invite link: {:?}", e),
+        None => {}
+    }
+}
+
+/// Returns a shareable link for an invite, pointing at this client.
+pub fn invite_link(invite: &Invite) -> Option<Url> {
+    link_base().map(|base| invite.to_link(&base))
+}
+
+#[cfg(target_family = "wasm")]
+fn startup_link() -> Option<String> {
+    gloo_utils::window().location().href().ok()
+}
+
+/// Address of this page, `None` if the browser does not tell it.
+#[cfg(target_family = "wasm")]
+pub fn link_base() -> Option<Url> {
+    startup_link().and_then(|href| Url::parse(&href).ok())
+}
+
+/// Accepts either a bare `whist://` argument, as passed by an url handler, or `--invite <link>`.
+#[cfg(not(target_family = "wasm"))]
+fn startup_link() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg.starts_with(&format!("{INVITE_SCHEME}://")) {
+            return Some(arg);
+        }
+        if arg == INVITE_FLAG {
+            return args.next();
+        }
+        if let Some(link) = arg.strip_prefix(&format!("{INVITE_FLAG}=")) {
+            return Some(link.to_owned());
+        }
+    }
+    None
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn link_base() -> Option<Url> {
+    Url::parse(&format!("{INVITE_SCHEME}://join")).ok()
+}
@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+use crate::MySystemSets;
+use crate::card::VariantKind;
+use crate::lobby::CurrentRoom;
+use crate::network::{NetworkCommand, PasswordRequest, PlayerRequest, RoomSettings, TrickHistory};
+
+const MIN_PLAYER: u8 = 2;
+const MAX_PLAYER: u8 = 8;
+
+/// Shows the host-only room controls in the lobby and ingame.
+pub struct HostControlsPlugin;
+
+impl Plugin for HostControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            host_window
+                .run_if(resource_exists::<CurrentRoom>)
+                .in_set(MySystemSets::Egui),
+        );
+    }
+}
+
+#[derive(Default)]
+struct UiState {
+    password: String,
+    /// Settings being edited, initialised from the room when first shown.
+    settings: Option<RoomSettings>,
+}
+
+fn host_window(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiState>,
+    room: Res<CurrentRoom>,
+    mut event_writer: EventWriter<NetworkCommand>,
+) {
+    if room.is_added() {
+        *ui_state = default();
+    }
+    if !room.is_host() {
+        return;
+    }
+    let Some(info) = &room.info else {
+        return;
+    };
+    let ui_state: &mut UiState = &mut ui_state;
+    egui::Window::new("Host")
+        .default_open(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            egui::Grid::new("host_players").show(ui, |ui| {
+                for player in &info.players {
+                    let is_me = room.user_id.as_ref() == Some(&player.user_id);
+                    ui.label(&player.username);
+                    if ui.add_enabled(!is_me, egui::Button::new("Kick")).clicked() {
+                        event_writer.send(NetworkCommand::GameKick(
+                            room.room_id.to_owned(),
+                            PlayerRequest {
+                                user_id: player.user_id.to_owned(),
+                            },
+                        ));
+                    }
+                    if ui
+                        .add_enabled(!is_me, egui::Button::new("Make host"))
+                        .clicked()
+                    {
+                        event_writer.send(NetworkCommand::GameTransferHost(
+                            room.room_id.to_owned(),
+                            PlayerRequest {
+                                user_id: player.user_id.to_owned(),
+                            },
+                        ));
+                    }
+                    ui.end_row();
+                }
+            });
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label("Password:");
+                ui.add(egui::TextEdit::singleline(&mut ui_state.password).password(true));
+                if ui.button("Set").clicked() {
+                    event_writer.send(NetworkCommand::GamePassword(
+                        room.room_id.to_owned(),
+                        PasswordRequest {
+                            password: if ui_state.password.is_empty() {
+                                None
+                            } else {
+                                Some(ui_state.password.to_string())
+                            },
+                        },
+                    ));
+                    ui_state.password.clear();
+                }
+            });
+
+            ui.separator();
+            let mut allow_spectators = info.settings.allow_spectators;
+            if ui
+                .checkbox(&mut allow_spectators, "Allow spectators")
+                .changed()
+            {
+                event_writer.send(NetworkCommand::GameSettings(
+                    room.room_id.to_owned(),
+                    RoomSettings {
+                        allow_spectators,
+                        ..info.settings.to_owned()
+                    },
+                ));
+            }
+
+            if !info.started {
+                ui.separator();
+                let settings = ui_state
+                    .settings
+                    .get_or_insert_with(|| info.settings.clone());
+                // toggled on its own above
+                settings.allow_spectators = info.settings.allow_spectators;
+                let variant = settings.variant;
+                egui::ComboBox::from_label("Variant")
+                    .selected_text(variant.to_string())
+                    .show_ui(ui, |ui| {
+                        for kind in VariantKind::ALL {
+                            ui.selectable_value(&mut settings.variant, kind, kind.to_string());
+                        }
+                    });
+                ui.checkbox(&mut settings.honours, "Score honours");
+                ui.horizontal(|ui| {
+                    ui.label("Trick log:");
+                    ui.radio_value(&mut settings.trick_history, TrickHistory::Full, "Full");
+                    ui.radio_value(
+                        &mut settings.trick_history,
+                        TrickHistory::LastTrick,
+                        "Last trick only",
+                    );
+                });
+                let players = settings.variant.rules().players();
+                let min_player = MIN_PLAYER.max(*players.start());
+                let max_player = MAX_PLAYER.min(*players.end());
+                if settings.variant != variant {
+                    settings.min_player = settings.min_player.clamp(min_player, max_player);
+                    settings.max_player = settings.max_player.clamp(min_player, max_player);
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Min players:");
+                    ui.add(
+                        egui::DragValue::new(&mut settings.min_player)
+                            .range(min_player..=settings.max_player),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Max players:");
+                    ui.add(
+                        egui::DragValue::new(&mut settings.max_player)
+                            .range(settings.min_player..=max_player),
+                    );
+                });
+                let button = ui.add_enabled(*settings != info.settings, egui::Button::new("Apply"));
+                if button.clicked() {
+                    event_writer.send(NetworkCommand::GameSettings(
+                        room.room_id.to_owned(),
+                        settings.to_owned(),
+                    ));
+                    ui_state.settings = None;
+                }
+            }
+        });
+}
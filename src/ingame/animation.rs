@@ -0,0 +1,334 @@
+use std::fmt::{Display, Formatter};
+
+use bevy::prelude::*;
+use bevy_egui::egui::Rect;
+
+use super::table::Table;
+use crate::card::Trick;
+use crate::lobby::CurrentRoom;
+use crate::network::NetworkEvent;
+
+const DEAL_SECONDS: f32 = 0.25;
+/// Delay between two dealt cards.
+const DEAL_STAGGER_SECONDS: f32 = 0.04;
+const PLAY_SECONDS: f32 = 0.3;
+/// Time a complete trick stays on the table before it is collected.
+const COLLECT_DELAY_SECONDS: f32 = 0.8;
+const COLLECT_SECONDS: f32 = 0.4;
+/// With more game events than this in one frame, e.g. after a reconnect, animations are skipped.
+const BURST_EVENTS: usize = 4;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimationSpeed {
+    Instant,
+    Fast,
+    #[default]
+    Normal,
+    Slow,
+}
+
+impl AnimationSpeed {
+    pub const ALL: [AnimationSpeed; 4] = [
+        AnimationSpeed::Instant,
+        AnimationSpeed::Fast,
+        AnimationSpeed::Normal,
+        AnimationSpeed::Slow,
+    ];
+
+    /// Factor applied to every duration.
+    fn scale(&self) -> f32 {
+        match self {
+            AnimationSpeed::Instant => 0.0,
+            AnimationSpeed::Fast => 0.5,
+            AnimationSpeed::Normal => 1.0,
+            AnimationSpeed::Slow => 2.0,
+        }
+    }
+}
+
+impl Display for AnimationSpeed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnimationSpeed::Instant => write!(f, "Instant"),
+            AnimationSpeed::Fast => write!(f, "Fast"),
+            AnimationSpeed::Normal => write!(f, "Normal"),
+            AnimationSpeed::Slow => write!(f, "Slow"),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationSettings {
+    pub speed: AnimationSpeed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Motion {
+    /// A card dealt from the dealer to a seat, `index` is its position in the hand.
+    Deal { seat: u8, index: usize },
+    /// A card played from a hand into the trick.
+    Play { seat: u8 },
+    /// The last trick sweeping to the winner.
+    Collect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Progress {
+    /// The card is not on its way yet.
+    Waiting,
+    /// Eased progress from 0 to 1.
+    Moving(f32),
+}
+
+impl Progress {
+    /// Rect of a card moving from `from` to `to`.
+    pub fn lerp(&self, from: Rect, to: Rect) -> Rect {
+        let t = match self {
+            Progress::Waiting => 0.0,
+            Progress::Moving(t) => *t,
+        };
+        Rect::from_min_max(from.min.lerp(to.min, t), from.max.lerp(to.max, t))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Tween {
+    motion: Motion,
+    delay: f32,
+    duration: f32,
+    elapsed: f32,
+}
+
+impl Tween {
+    fn new(motion: Motion, delay: f32, duration: f32) -> Self {
+        Self {
+            motion,
+            delay,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    fn progress(&self) -> Progress {
+        if self.elapsed < self.delay {
+            return Progress::Waiting;
+        }
+        let t = ((self.elapsed - self.delay) / self.duration).clamp(0.0, 1.0);
+        Progress::Moving(t * t * (3.0 - 2.0 * t))
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.delay + self.duration
+    }
+}
+
+/// Cards currently moving on the table, the table view draws them in between their positions.
+#[derive(Resource, Debug, Default)]
+pub struct Animations {
+    tweens: Vec<Tween>,
+    /// Trick being swept to the winner, it is already gone from the table.
+    collected: Option<(Trick, u8)>,
+}
+
+impl Animations {
+    fn find(&self, motion: Motion) -> Option<Progress> {
+        self.tweens
+            .iter()
+            .find(|tween| tween.motion == motion)
+            .map(Tween::progress)
+    }
+
+    pub fn deal(&self, seat: u8, index: usize) -> Option<Progress> {
+        self.find(Motion::Deal { seat, index })
+    }
+
+    pub fn play(&self, seat: u8) -> Option<Progress> {
+        self.find(Motion::Play { seat })
+    }
+
+    pub fn collect(&self) -> Option<(Progress, &Trick, u8)> {
+        let progress = self.find(Motion::Collect)?;
+        let (trick, winner) = self.collected.as_ref()?;
+        Some((progress, trick, *winner))
+    }
+
+    pub fn is_dealing(&self) -> bool {
+        self.tweens
+            .iter()
+            .any(|tween| matches!(tween.motion, Motion::Deal { .. }))
+    }
+
+    /// Ends every animation, cards jump to where they belong.
+    pub fn skip(&mut self) {
+        self.tweens.clear();
+        self.collected = None;
+    }
+
+    fn start(&mut self, tween: Tween) {
+        self.tweens.retain(|other| other.motion != tween.motion);
+        self.tweens.push(tween);
+    }
+
+    /// Starts the animations of an event, `table` already contains its changes.
+    fn on_event(&mut self, event: &NetworkEvent, table: &Table, my_seat: Option<u8>, scale: f32) {
+        match event {
+            NetworkEvent::HandDealt(hand_dealt) => {
+                self.skip();
+                let seats = table.hands.len().max(1) as u8;
+                for (seat, hand) in (0..seats).zip(&table.hands) {
+                    // dealing starts left of the dealer
+                    let order = (seat + 2 * seats - hand_dealt.dealer % seats - 1) % seats;
+                    for index in 0..hand.len() {
+                        let step = index * seats as usize + order as usize;
+                        self.start(Tween::new(
+                            Motion::Deal { seat, index },
+                            step as f32 * DEAL_STAGGER_SECONDS * scale,
+                            DEAL_SECONDS * scale,
+                        ));
+                    }
+                }
+            }
+            // our own cards are moved by the hand view right away
+            NetworkEvent::CardPlayed(card_played) if Some(card_played.seat) != my_seat => {
+                self.start(Tween::new(
+                    Motion::Play {
+                        seat: card_played.seat,
+                    },
+                    0.0,
+                    PLAY_SECONDS * scale,
+                ));
+            }
+            NetworkEvent::TrickTaken(_) => {
+                self.tweens
+                    .retain(|tween| !matches!(tween.motion, Motion::Play { .. }));
+                self.collected = table.tricks.last().cloned();
+                self.start(Tween::new(
+                    Motion::Collect,
+                    COLLECT_DELAY_SECONDS * scale,
+                    COLLECT_SECONDS * scale,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    fn advance(&mut self, seconds: f32) {
+        for tween in &mut self.tweens {
+            tween.elapsed += seconds;
+        }
+        self.tweens.retain(|tween| !tween.is_finished());
+        if self.find(Motion::Collect).is_none() {
+            self.collected = None;
+        }
+    }
+}
+
+fn is_game_event(event: &&NetworkEvent) -> bool {
+    matches!(
+        event,
+        NetworkEvent::HandDealt(_)
+            | NetworkEvent::CardPlayed(_)
+            | NetworkEvent::TrickTaken(_)
+            | NetworkEvent::Turn(_)
+    )
+}
+
+/// Has to run after the table took the events in.
+pub fn animate_game_events(
+    mut animations: ResMut<Animations>,
+    settings: Res<AnimationSettings>,
+    table: Res<Table>,
+    room: Option<Res<CurrentRoom>>,
+    mut network_events: EventReader<NetworkEvent>,
+) {
+    let events: Vec<&NetworkEvent> = network_events.read().filter(is_game_event).collect();
+    if settings.speed == AnimationSpeed::Instant || events.len() > BURST_EVENTS {
+        animations.skip();
+        return;
+    }
+    let my_seat = room.and_then(|room| room.me().and_then(|me| me.seat));
+    for event in events {
+        animations.on_event(event, &table, my_seat, settings.speed.scale());
+    }
+}
+
+pub fn advance_animations(time: Res<Time>, mut animations: ResMut<Animations>) {
+    animations.advance(time.delta_secs());
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::card::{Card, CardVariant, Rank, Suit};
+    use crate::network::{CardPlayedEvent, HandDealtEvent, TrickTakenEvent};
+
+    const CARD: Card = Card {
+        suit: Suit::Clubs,
+        rank: Rank::Num7,
+    };
+
+    fn dealt_table() -> (Table, NetworkEvent) {
+        let event = NetworkEvent::HandDealt(HandDealtEvent {
+            dealer: 1,
+            hands: vec![vec![CardVariant::Back; 2]; 2],
+            trump: None,
+            turned_up: None,
+        });
+        let mut table = Table::default();
+        table.apply(&event);
+        (table, event)
+    }
+
+    #[test]
+    fn test_deal_starts_left_of_dealer() {
+        let (table, event) = dealt_table();
+        let mut animations = Animations::default();
+        animations.on_event(&event, &table, None, 1.0);
+        assert!(animations.is_dealing());
+        assert_eq!(Some(Progress::Moving(0.0)), animations.deal(0, 0));
+        assert_eq!(Some(Progress::Waiting), animations.deal(1, 0));
+
+        animations.advance(10.0);
+        assert!(!animations.is_dealing());
+        assert_eq!(None, animations.deal(1, 1));
+    }
+
+    #[test]
+    fn test_own_play_is_not_animated() {
+        let (table, _) = dealt_table();
+        let mut animations = Animations::default();
+        let event = NetworkEvent::CardPlayed(CardPlayedEvent {
+            seat: 0,
+            card: CARD,
+        });
+        animations.on_event(&event, &table, Some(0), 1.0);
+        assert_eq!(None, animations.play(0));
+        animations.on_event(&event, &table, Some(1), 1.0);
+        assert_eq!(Some(Progress::Moving(0.0)), animations.play(0));
+    }
+
+    #[test]
+    fn test_collect_keeps_trick_until_finished() {
+        let (mut table, _) = dealt_table();
+        let mut animations = Animations::default();
+        for event in [
+            NetworkEvent::CardPlayed(CardPlayedEvent {
+                seat: 0,
+                card: CARD,
+            }),
+            NetworkEvent::TrickTaken(TrickTakenEvent { winner: 0 }),
+        ] {
+            table.apply(&event);
+            animations.on_event(&event, &table, None, 1.0);
+        }
+        let (progress, trick, winner) = animations.collect().unwrap();
+        assert_eq!(Progress::Waiting, progress);
+        assert_eq!(Some(CARD), trick.card_of(0));
+        assert_eq!(0, winner);
+
+        animations.advance(COLLECT_DELAY_SECONDS + COLLECT_SECONDS);
+        assert!(animations.collect().is_none());
+    }
+}
@@ -0,0 +1,511 @@
+use std::fmt::{Display, Formatter};
+
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont, point};
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+use bevy_egui::egui::{self, Color32, ColorImage, Id, Pos2, Rect, TextureHandle, TextureId, pos2};
+
+use super::table::suit_color;
+use super::theme::{CardTheme, Themes};
+use crate::card::{Card, CardVariant};
+use crate::ui::{MONOSPACE_FONT, PROPORTIONAL_FONT};
+
+/// Cards in one row of the atlas, every suit gets a row and the backs share the last one.
+const COLUMNS: usize = 13;
+const ROWS: usize = 5;
+pub const FACES: usize = 52;
+/// Largest card the table draws, in points.
+const MAX_CARD_HEIGHT: f32 = 120.0;
+const CARD_ASPECT: f32 = 0.7;
+const BORDER_COLOR: Color32 = Color32::DARK_GRAY;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CardBack {
+    #[default]
+    Lattice,
+    Checker,
+    Stripes,
+}
+
+impl CardBack {
+    pub const ALL: [CardBack; 3] = [CardBack::Lattice, CardBack::Checker, CardBack::Stripes];
+
+    fn colors(&self) -> (Color32, Color32) {
+        match self {
+            CardBack::Lattice => (
+                Color32::from_rgb(0x2a, 0x4a, 0x9a),
+                Color32::from_rgb(0x6a, 0x8a, 0xda),
+            ),
+            CardBack::Checker => (
+                Color32::from_rgb(0x9a, 0x1a, 0x2a),
+                Color32::from_rgb(0x6a, 0x0a, 0x1a),
+            ),
+            CardBack::Stripes => (
+                Color32::from_rgb(0x1a, 0x6a, 0x3a),
+                Color32::from_rgb(0x4a, 0x9a, 0x6a),
+            ),
+        }
+    }
+
+    /// Whether the pattern uses its second colour at a pixel, `unit` is a fraction of the card height.
+    fn is_accent(&self, x: f32, y: f32, unit: f32) -> bool {
+        match self {
+            CardBack::Lattice => {
+                let period = 6.0 * unit;
+                (x + y).rem_euclid(period) < unit || (x - y).rem_euclid(period) < unit
+            }
+            CardBack::Checker => {
+                let size = 4.0 * unit;
+                ((x / size).floor() + (y / size).floor()) as i32 % 2 == 0
+            }
+            CardBack::Stripes => (x + y).rem_euclid(5.0 * unit) < 2.0 * unit,
+        }
+    }
+}
+
+impl Display for CardBack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardBack::Lattice => write!(f, "Lattice"),
+            CardBack::Checker => write!(f, "Checker"),
+            CardBack::Stripes => write!(f, "Stripes"),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct CardFaceSettings {
+    pub back: CardBack,
+    /// Directory of a theme below `assets/themes`, the built-in faces are drawn without one.
+    pub theme: Option<String>,
+}
+
+fn cell_of(card: &CardVariant, back: CardBack) -> usize {
+    match card {
+        CardVariant::Front(card) => {
+            let index: u8 = (*card).into();
+            index as usize
+        }
+        CardVariant::Back => FACES + back as usize,
+    }
+}
+
+/// Blends `color` over `dst` with the given coverage, both premultiplied.
+fn blend(dst: Color32, color: Color32, coverage: f32) -> Color32 {
+    let keep = 1.0 - coverage * color.a() as f32 / 255.0;
+    let channel = |dst: u8, src: u8| (dst as f32 * keep + src as f32 * coverage).round() as u8;
+    Color32::from_rgba_premultiplied(
+        channel(dst.r(), color.r()),
+        channel(dst.g(), color.g()),
+        channel(dst.b(), color.b()),
+        channel(dst.a(), color.a()),
+    )
+}
+
+/// Coverage of the pixel at `p` by a rounded rect, smoothed over one pixel.
+fn rounded_rect_coverage(rect: Rect, radius: f32, p: Pos2) -> f32 {
+    let half = rect.size() / 2.0 - egui::vec2(radius, radius);
+    let q = (p - rect.center()).abs() - half;
+    let outside = q.max(egui::Vec2::ZERO).length() + q.x.max(q.y).min(0.0) - radius;
+    (0.5 - outside).clamp(0.0, 1.0)
+}
+
+struct Canvas<'a> {
+    image: ColorImage,
+    cell_size: [usize; 2],
+    /// Tried in order for every character, only the monospace font has the suit symbols.
+    fonts: Vec<FontRef<'a>>,
+}
+
+impl Canvas<'_> {
+    fn plot(&mut self, cell: usize, x: i32, y: i32, color: Color32, coverage: f32) {
+        let [width, height] = self.cell_size;
+        if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+            return;
+        }
+        let column = (cell % COLUMNS) * width + x as usize;
+        let row = (cell / COLUMNS) * height + y as usize;
+        let pixel = &mut self.image.pixels[row * self.image.size[0] + column];
+        *pixel = blend(*pixel, color, coverage);
+    }
+
+    fn cell_rect(&self) -> Rect {
+        Rect::from_min_size(
+            Pos2::ZERO,
+            egui::vec2(self.cell_size[0] as f32, self.cell_size[1] as f32),
+        )
+    }
+
+    /// Fills a rounded rect, `paint` picks the colour of each pixel.
+    fn fill(
+        &mut self,
+        cell: usize,
+        rect: Rect,
+        radius: f32,
+        mut paint: impl FnMut(f32, f32) -> Color32,
+    ) {
+        let [width, height] = self.cell_size;
+        for y in 0..height {
+            for x in 0..width {
+                let p = pos2(x as f32 + 0.5, y as f32 + 0.5);
+                let coverage = rounded_rect_coverage(rect, radius, p);
+                if coverage > 0.0 {
+                    self.plot(cell, x as i32, y as i32, paint(p.x, p.y), coverage);
+                }
+            }
+        }
+    }
+
+    /// Draws text centred on `anchor`, or rotated by half a turn around the cell centre.
+    fn text(
+        &mut self,
+        cell: usize,
+        text: &str,
+        size: f32,
+        anchor: Pos2,
+        color: Color32,
+        rotated: bool,
+    ) {
+        let mut caret = 0.0;
+        let mut outlines = Vec::new();
+        for c in text.chars() {
+            let Some(font) = self.fonts.iter().find(|font| font.glyph_id(c).0 != 0) else {
+                continue;
+            };
+            let font = font.as_scaled(PxScale::from(size));
+            let mut glyph = font.scaled_glyph(c);
+            glyph.position = point(caret, font.ascent());
+            caret += font.h_advance(glyph.id);
+            outlines.extend(font.outline_glyph(glyph));
+        }
+        let Some(bounds) = outlines
+            .iter()
+            .map(|outline| outline.px_bounds())
+            .reduce(|a, b| ab_glyph::Rect {
+                min: point(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
+                max: point(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
+            })
+        else {
+            return;
+        };
+        let offset_x = anchor.x - (bounds.min.x + bounds.max.x) / 2.0;
+        let offset_y = anchor.y - (bounds.min.y + bounds.max.y) / 2.0;
+        let [width, height] = self.cell_size;
+        for outline in outlines {
+            let min = outline.px_bounds().min;
+            outline.draw(|x, y, coverage| {
+                let mut x = (min.x + offset_x).round() as i32 + x as i32;
+                let mut y = (min.y + offset_y).round() as i32 + y as i32;
+                if rotated {
+                    x = width as i32 - 1 - x;
+                    y = height as i32 - 1 - y;
+                }
+                self.plot(cell, x, y, color, coverage);
+            });
+        }
+    }
+
+    fn face(&mut self, card: Card) {
+        let cell = cell_of(&CardVariant::Front(card), CardBack::default());
+        let rect = self.cell_rect();
+        let height = rect.height();
+        let radius = rect.width() * 0.08;
+        let border = (height / 120.0).max(1.0);
+        self.fill(cell, rect, radius, |_, _| BORDER_COLOR);
+        self.fill(cell, rect.shrink(border), radius, |_, _| Color32::WHITE);
+
+        let color = suit_color(card.suit);
+        let rank = card.rank.letter().to_string();
+        let suit = card.suit.symbol().to_string();
+        let corner_x = rect.width() * 0.17;
+        for rotated in [false, true] {
+            self.text(
+                cell,
+                &rank,
+                height * 0.24,
+                pos2(corner_x, height * 0.14),
+                color,
+                rotated,
+            );
+            self.text(
+                cell,
+                &suit,
+                height * 0.22,
+                pos2(corner_x, height * 0.31),
+                color,
+                rotated,
+            );
+        }
+        self.text(cell, &suit, height * 0.5, rect.center(), color, false);
+    }
+
+    fn back(&mut self, back: CardBack) {
+        let cell = cell_of(&CardVariant::Back, back);
+        let rect = self.cell_rect();
+        let radius = rect.width() * 0.08;
+        let border = (rect.height() / 120.0).max(1.0);
+        let unit = rect.height() / 60.0;
+        let (base, accent) = back.colors();
+        self.fill(cell, rect, radius, |_, _| BORDER_COLOR);
+        self.fill(cell, rect.shrink(border), radius, |_, _| Color32::WHITE);
+        self.fill(cell, rect.shrink(4.0 * unit), radius / 2.0, |x, y| {
+            if back.is_accent(x, y, unit) {
+                accent
+            } else {
+                base
+            }
+        });
+    }
+}
+
+/// Tallest cell whose atlas still fits into a texture of `max_texture_side` pixels.
+///
+/// WebGL2 only guarantees 2048 pixels, which a HiDPI browser reaches at full card size.
+fn max_cell_height(max_texture_side: usize) -> usize {
+    let cell_width = max_texture_side / COLUMNS;
+    // the width is rounded from the height, so it may not round up past the limit
+    ((cell_width as f32 / CARD_ASPECT).floor() as usize).min(max_texture_side / ROWS)
+}
+
+/// Renders every card face and back into one image, cells are `cell_height` pixels high.
+pub fn render_atlas(cell_height: usize) -> ColorImage {
+    let cell_size = [
+        (cell_height as f32 * CARD_ASPECT).round() as usize,
+        cell_height,
+    ];
+    let mut canvas = Canvas {
+        image: ColorImage::new(
+            [cell_size[0] * COLUMNS, cell_size[1] * ROWS],
+            Color32::TRANSPARENT,
+        ),
+        cell_size,
+        fonts: [PROPORTIONAL_FONT, MONOSPACE_FONT]
+            .into_iter()
+            .map(|font| FontRef::try_from_slice(font).expect("bundled fonts are valid"))
+            .collect(),
+    };
+    for index in 0..FACES as u8 {
+        if let Ok(card) = Card::try_from(index) {
+            canvas.face(card);
+        }
+    }
+    for back in CardBack::ALL {
+        canvas.back(back);
+    }
+    canvas.image
+}
+
+/// Texture coordinates of a cell of the built-in atlas.
+fn cell_uv(cell: usize) -> Rect {
+    let size = egui::vec2(1.0 / COLUMNS as f32, 1.0 / ROWS as f32);
+    let min = pos2(
+        (cell % COLUMNS) as f32 * size.x,
+        (cell / COLUMNS) as f32 * size.y,
+    );
+    Rect::from_min_size(min, size)
+}
+
+/// Where the card images are, kept in egui memory so every painter can reach it.
+#[derive(Debug, Clone, Copy)]
+pub struct CardAtlas {
+    faces: TextureId,
+    face_uvs: [Rect; FACES],
+    back: TextureId,
+    back_uv: Rect,
+    /// Colour of the table, themes may bring their own felt.
+    pub felt: Option<Color32>,
+}
+
+impl CardAtlas {
+    fn id() -> Id {
+        Id::new("card_atlas")
+    }
+
+    pub fn get(ctx: &egui::Context) -> Option<Self> {
+        ctx.data(|data| data.get_temp(Self::id()))
+    }
+
+    fn built_in(texture: TextureId, back: CardBack) -> Self {
+        Self {
+            faces: texture,
+            face_uvs: std::array::from_fn(cell_uv),
+            back: texture,
+            back_uv: cell_uv(cell_of(&CardVariant::Back, back)),
+            felt: None,
+        }
+    }
+
+    fn themed(theme: &CardTheme, faces: TextureId, faces_size: UVec2, back: TextureId) -> Self {
+        let size = faces_size.as_vec2();
+        let face_uvs = std::array::from_fn(|index| {
+            let rect = theme.cards[index];
+            Rect::from_min_max(
+                pos2(rect.min.x as f32 / size.x, rect.min.y as f32 / size.y),
+                pos2(rect.max.x as f32 / size.x, rect.max.y as f32 / size.y),
+            )
+        });
+        Self {
+            faces,
+            face_uvs,
+            back,
+            back_uv: Rect::from_min_max(Pos2::ZERO, pos2(1.0, 1.0)),
+            felt: theme.felt.map(|[r, g, b]| Color32::from_rgb(r, g, b)),
+        }
+    }
+
+    /// Texture and texture coordinates of a card.
+    pub fn image(&self, card: &CardVariant) -> (TextureId, Rect) {
+        match card {
+            CardVariant::Front(_) => (
+                self.faces,
+                self.face_uvs[cell_of(card, CardBack::default())],
+            ),
+            CardVariant::Back => (self.back, self.back_uv),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct CardFaces {
+    /// Dropping the handle frees the texture.
+    texture: TextureHandle,
+    pixels_per_point: f32,
+}
+
+/// Renders the atlas on startup and again whenever the UI scale changes.
+///
+/// A selected theme replaces it once all its images are loaded.
+pub fn update_card_faces(
+    mut commands: Commands,
+    mut egui_context: EguiContexts,
+    faces: Option<Res<CardFaces>>,
+    settings: Res<CardFaceSettings>,
+    themes: Option<Res<Themes>>,
+    theme_assets: Res<Assets<CardTheme>>,
+    images: Res<Assets<Image>>,
+) {
+    let themed = themes
+        .as_ref()
+        .and_then(|themes| themes.selected())
+        .and_then(|handle| theme_assets.get(handle))
+        .and_then(|theme| {
+            let size = images.get(&theme.atlas)?.size();
+            // the settings show why the built-in faces are used instead
+            theme.check_atlas(size).ok()?;
+            images.get(&theme.back)?;
+            Some(CardAtlas::themed(
+                theme,
+                egui_context.add_image(theme.atlas.clone_weak()),
+                size,
+                egui_context.add_image(theme.back.clone_weak()),
+            ))
+        });
+    let ctx = egui_context.ctx_mut();
+    let pixels_per_point = ctx.pixels_per_point();
+    let max_texture_side = ctx.input(|i| i.max_texture_side);
+    let texture = match faces {
+        Some(faces) if faces.pixels_per_point == pixels_per_point => faces.texture.id(),
+        _ => {
+            let cell_height = ((MAX_CARD_HEIGHT * pixels_per_point).ceil() as usize)
+                .min(max_cell_height(max_texture_side));
+            info!("rendering card faces at {cell_height} pixels");
+            let texture = ctx.load_texture(
+                "card_faces",
+                render_atlas(cell_height),
+                egui::TextureOptions::LINEAR,
+            );
+            let id = texture.id();
+            commands.insert_resource(CardFaces {
+                texture,
+                pixels_per_point,
+            });
+            id
+        }
+    };
+    let atlas = themed.unwrap_or_else(|| CardAtlas::built_in(texture, settings.back));
+    ctx.data_mut(|data| data.insert_temp(CardAtlas::id(), atlas));
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::card::{Rank, Suit};
+
+    const CELL_HEIGHT: usize = 60;
+
+    fn cell_pixel(image: &ColorImage, cell: usize, x: usize, y: usize) -> Color32 {
+        let width = image.size[0] / COLUMNS;
+        let height = image.size[1] / ROWS;
+        let column = (cell % COLUMNS) * width + x;
+        let row = (cell / COLUMNS) * height + y;
+        image.pixels[row * image.size[0] + column]
+    }
+
+    #[test]
+    fn test_fonts_have_suit_symbols() {
+        let font = FontRef::try_from_slice(MONOSPACE_FONT).unwrap();
+        for suit in [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades] {
+            assert_ne!(0, font.glyph_id(suit.symbol()).0, "{suit}");
+        }
+    }
+
+    #[test]
+    fn test_render_atlas() {
+        let image = render_atlas(CELL_HEIGHT);
+        assert_eq!([42 * COLUMNS, CELL_HEIGHT * ROWS], image.size);
+
+        let card = CardVariant::Front(Card {
+            suit: Suit::Hearts,
+            rank: Rank::Ace,
+        });
+        let cell = cell_of(&card, CardBack::default());
+        assert_eq!(Color32::TRANSPARENT, cell_pixel(&image, cell, 0, 0));
+        assert_eq!(Color32::WHITE, cell_pixel(&image, cell, 4, CELL_HEIGHT / 2));
+        let center = cell_pixel(&image, cell, 21, CELL_HEIGHT / 2);
+        assert!(center.r() > center.g(), "{center:?}");
+    }
+
+    #[test]
+    fn test_atlas_fits_texture_limit() {
+        for max_texture_side in [1024, 2048, 2049, 4096, 8192, 16384] {
+            let height = max_cell_height(max_texture_side);
+            let width = (height as f32 * CARD_ASPECT).round() as usize;
+            assert!(width * COLUMNS <= max_texture_side, "{max_texture_side}");
+            assert!(height * ROWS <= max_texture_side, "{max_texture_side}");
+        }
+        assert_eq!(224, max_cell_height(2048));
+    }
+
+    #[test]
+    fn test_backs_differ() {
+        let image = render_atlas(CELL_HEIGHT);
+        let backs: Vec<Vec<Color32>> = CardBack::ALL
+            .iter()
+            .map(|back| {
+                let cell = cell_of(&CardVariant::Back, *back);
+                (0..CELL_HEIGHT)
+                    .map(|y| cell_pixel(&image, cell, 21, y))
+                    .collect()
+            })
+            .collect();
+        assert_ne!(backs[0], backs[1]);
+        assert_ne!(backs[1], backs[2]);
+    }
+
+    #[test]
+    fn test_atlas_uv() {
+        let atlas = CardAtlas::built_in(TextureId::default(), CardBack::Checker);
+        let (_, uv) = atlas.image(&CardVariant::Back);
+        assert_eq!(pos2(1.0 / 13.0, 0.8), uv.min);
+        let (_, uv) = atlas.image(&CardVariant::Front(Card {
+            suit: Suit::Clubs,
+            rank: Rank::Num2,
+        }));
+        assert_eq!(
+            Rect::from_min_size(Pos2::ZERO, egui::vec2(1.0 / 13.0, 0.2)),
+            uv
+        );
+    }
+}
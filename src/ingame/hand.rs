@@ -0,0 +1,308 @@
+use bevy::prelude::*;
+use bevy_egui::egui::{
+    self, Color32, Id, LayerId, Order, Painter, Pos2, Rect, Sense, Ui, Vec2, pos2, vec2,
+};
+
+use super::table::{TablePosition, hand_card_rects, paint_card};
+use crate::card::{Card, CardContainer, CardVariant, OrderedCards, SortMode, Suit, UnorderedCards};
+use crate::lobby::CurrentRoom;
+use crate::network::{GamePlayResult, NetworkEvent};
+
+const TOAST_SECONDS: f32 = 4.0;
+const ILLEGAL_CARD_TINT: Color32 = Color32::from_black_alpha(110);
+const DROP_AREA_COLOR: Color32 = Color32::from_rgba_premultiplied(24, 24, 24, 24);
+/// How far a hovered or selected card is lifted, relative to the card height.
+const LIFT: f32 = 0.15;
+
+/// Local state of playing a card from our own hand.
+#[derive(Resource, Debug, Default)]
+pub struct HandState {
+    /// Requires a double click to play a card, a single click only selects it.
+    pub confirm_double_click: bool,
+    /// Sent to the server and shown in the trick until it is confirmed or rejected.
+    pending: Option<Card>,
+    selected: Option<Card>,
+    toast: Option<Toast>,
+    /// Sorts the next hand in the suit order the cards were arranged in.
+    pub keep_arrangement: bool,
+    /// Order of our cards once the user moved one, sorted automatically until then.
+    arrangement: Option<OrderedCards>,
+    /// Suit order carried over from the last hand.
+    suit_order: Option<[Suit; 4]>,
+}
+
+impl HandState {
+    pub fn pending(&self) -> Option<Card> {
+        self.pending
+    }
+
+    /// Our cards in the order they are shown.
+    ///
+    /// Played cards leave the arrangement and new ones join at the end.
+    pub fn arrange(&mut self, cards: &UnorderedCards, trump: Option<Suit>) -> Vec<Card> {
+        if let Some(arrangement) = &mut self.arrangement {
+            arrangement.sync_with(cards);
+            return arrangement.iter().collect();
+        }
+        let mut sorted = OrderedCards::from_iter(cards.iter());
+        sorted.sort_by(match self.suit_order {
+            Some(order) => SortMode::Custom(order),
+            None => SortMode::Trump(trump),
+        });
+        sorted.into_iter().collect()
+    }
+
+    /// Goes back to sorting the hand automatically.
+    pub fn sort(&mut self) {
+        self.arrangement = None;
+        self.suit_order = None;
+    }
+
+    fn move_card(&mut self, cards: &[Card], card: Card, to: Card) {
+        let mut arrangement = OrderedCards::from_iter(cards.iter().copied());
+        if let (Some(from), Some(to)) = (arrangement.position(&card), arrangement.position(&to)) {
+            arrangement.move_card(from, to);
+        }
+        self.arrangement = Some(arrangement);
+    }
+
+    fn new_hand(&mut self) {
+        self.pending = None;
+        self.selected = None;
+        if !self.keep_arrangement {
+            self.suit_order = None;
+        } else if let Some(arrangement) = &self.arrangement {
+            self.suit_order = Some(arrangement.suit_order());
+        }
+        self.arrangement = None;
+    }
+}
+
+#[derive(Debug)]
+struct Toast {
+    text: String,
+    until: f32,
+}
+
+pub fn track_play_results(
+    mut hand: ResMut<HandState>,
+    room: Res<CurrentRoom>,
+    time: Res<Time>,
+    mut network_events: EventReader<NetworkEvent>,
+    mut game_play_results: EventReader<GamePlayResult>,
+) {
+    let my_seat = room.me().and_then(|me| me.seat);
+    for network_event in network_events.read() {
+        match network_event {
+            NetworkEvent::CardPlayed(card_played) if Some(card_played.seat) == my_seat => {
+                hand.pending = None;
+            }
+            NetworkEvent::HandDealt(_) => hand.new_hand(),
+            _ => {}
+        }
+    }
+    for GamePlayResult(result) in game_play_results.read() {
+        if let Err(e) = result {
+            warn!("card rejected: {:?}", e);
+            // the card snaps back into the hand
+            hand.pending = None;
+            hand.toast = Some(Toast {
+                text: format!("Card rejected: {e}"),
+                until: time.elapsed_secs() + TOAST_SECONDS,
+            });
+        }
+    }
+    if hand
+        .toast
+        .as_ref()
+        .is_some_and(|toast| toast.until < time.elapsed_secs())
+    {
+        hand.toast = None;
+    }
+}
+
+/// Area in the middle of the table a card can be dropped on to play it.
+pub fn trick_area(table_rect: Rect, card_size: Vec2) -> Rect {
+    Rect::from_center_size(table_rect.center(), card_size * vec2(3.4, 2.6))
+}
+
+/// Index of the card slot a card dragged to `pos` is dropped into, if `pos` is on the hand.
+fn slot_at(rects: &[Rect], card_size: Vec2, pos: Pos2) -> Option<usize> {
+    let hand_area = rects
+        .iter()
+        .fold(Rect::NOTHING, |area, rect| area.union(*rect))
+        .expand2(vec2(card_size.x / 2.0, card_size.y * LIFT));
+    if !hand_area.contains(pos) {
+        return None;
+    }
+    rects
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| {
+            let a = (a.center().x - pos.x).abs();
+            let b = (b.center().x - pos.x).abs();
+            a.total_cmp(&b)
+        })
+        .map(|(i, _)| i)
+}
+
+/// Draws our own hand at the bottom of the table and returns the card the user chose to play.
+///
+/// `playable` is `None` while we may not play, otherwise cards outside of it are greyed out.
+/// Any card can be dragged to another place in the hand to rearrange it.
+pub fn hand_view(
+    ui: &mut Ui,
+    painter: &Painter,
+    table_rect: Rect,
+    card_size: Vec2,
+    arranged: &[Card],
+    playable: Option<&UnorderedCards>,
+    hand: &mut HandState,
+) -> Option<Card> {
+    let cards: Vec<Card> = arranged
+        .iter()
+        .copied()
+        .filter(|card| Some(*card) != hand.pending)
+        .collect();
+    let rects = hand_card_rects(table_rect, TablePosition::Bottom, card_size, cards.len());
+    let drop_area = trick_area(table_rect, card_size);
+    let mut played = None;
+    let mut dragged = None;
+    let mut moved = None;
+
+    for (i, (card, rect)) in cards.iter().zip(&rects).enumerate() {
+        let legal = playable.is_some_and(|playable| playable.contains(card));
+        // only the uncovered part of a card reacts to the pointer
+        let hit_rect = match rects.get(i + 1) {
+            Some(next) => Rect::from_min_max(rect.min, pos2(next.min.x, rect.max.y)),
+            None => *rect,
+        };
+        let response = ui.interact(
+            hit_rect,
+            Id::new(("hand_card", *card)),
+            Sense::click_and_drag(),
+        );
+
+        if response.dragged() {
+            dragged = Some((*card, legal));
+            continue;
+        }
+        if response.drag_stopped() {
+            let pos = ui.ctx().pointer_latest_pos();
+            if legal && pos.is_some_and(|pos| drop_area.contains(pos)) {
+                played = Some(*card);
+                continue;
+            }
+            if let Some(slot) = pos.and_then(|pos| slot_at(&rects, card_size, pos)) {
+                moved = Some((*card, cards[slot]));
+            }
+        }
+        if legal {
+            if hand.confirm_double_click {
+                if response.double_clicked() {
+                    played = Some(*card);
+                } else if response.clicked() {
+                    hand.selected = Some(*card);
+                }
+            } else if response.clicked() {
+                played = Some(*card);
+            }
+        }
+
+        let lifted = legal && (response.hovered() || hand.selected == Some(*card));
+        let rect = if lifted {
+            rect.translate(vec2(0.0, -card_size.y * LIFT))
+        } else {
+            *rect
+        };
+        paint_card(painter, rect, &CardVariant::Front(*card));
+        if playable.is_some() && !legal {
+            painter.rect_filled(rect, rect.width() * 0.08, ILLEGAL_CARD_TINT);
+        }
+    }
+
+    if let Some((card, legal)) = dragged {
+        if legal {
+            painter.rect_filled(drop_area, card_size.x * 0.2, DROP_AREA_COLOR);
+        }
+        if let Some(pos) = ui.ctx().pointer_latest_pos() {
+            let layer_painter = ui
+                .ctx()
+                .layer_painter(LayerId::new(Order::Tooltip, Id::new("dragged_card")));
+            paint_card(
+                &layer_painter,
+                Rect::from_center_size(pos, card_size),
+                &CardVariant::Front(card),
+            );
+        }
+    }
+
+    if let Some((card, to)) = moved.filter(|(card, to)| card != to) {
+        hand.move_card(arranged, card, to);
+    }
+    if played.is_some() {
+        hand.pending = played;
+        hand.selected = None;
+    }
+    played
+}
+
+pub fn toast_view(ctx: &egui::Context, hand: &HandState) {
+    let Some(toast) = &hand.toast else {
+        return;
+    };
+    egui::Area::new(Id::new("ingame_toast"))
+        .anchor(egui::Align2::CENTER_BOTTOM, vec2(0.0, -24.0))
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.colored_label(ui.visuals().error_fg_color, &toast.text);
+            });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn shorts(cards: &[Card]) -> Vec<String> {
+        cards.iter().map(Card::short).collect()
+    }
+
+    #[test]
+    fn test_arrangement_follows_played_cards() {
+        let mut hand = HandState::default();
+        let cards: UnorderedCards = "S:A H:K2 C:3".parse().unwrap();
+        let arranged = hand.arrange(&cards, None);
+        assert_eq!(vec!["3C", "KH", "2H", "AS"], shorts(&arranged));
+
+        hand.move_card(&arranged, "AS".parse().unwrap(), "3C".parse().unwrap());
+        let cards: UnorderedCards = "S:A H:2 C:3".parse().unwrap();
+        assert_eq!(vec!["AS", "3C", "2H"], shorts(&hand.arrange(&cards, None)));
+    }
+
+    #[test]
+    fn test_keep_arrangement_for_next_hand() {
+        let mut hand = HandState {
+            keep_arrangement: true,
+            ..default()
+        };
+        let cards: UnorderedCards = "S:A H:K C:3".parse().unwrap();
+        let arranged = hand.arrange(&cards, None);
+        hand.move_card(&arranged, "AS".parse().unwrap(), "3C".parse().unwrap());
+        hand.new_hand();
+
+        let cards: UnorderedCards = "S:Q H:J2 C:K".parse().unwrap();
+        assert_eq!(
+            vec!["QS", "KC", "JH", "2H"],
+            shorts(&hand.arrange(&cards, None))
+        );
+        hand.keep_arrangement = false;
+        hand.new_hand();
+        assert_eq!(
+            vec!["KC", "JH", "2H", "QS"],
+            shorts(&hand.arrange(&cards, None))
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use bevy_egui::egui::{self, Color32, RichText, Vec2};
+
+use super::table::{Table, card_label, paint_card, seat_name};
+use crate::card::{Card, CardVariant, SuitColor};
+use crate::network::RoomInfo;
+
+const PEEK_CARD_SIZE: Vec2 = Vec2::new(49.0, 70.0);
+const RED_SUIT_TEXT_COLOR: Color32 = Color32::from_rgb(0xff, 0x60, 0x60);
+
+/// Shows the cards of the previous trick until the window is closed.
+pub fn last_trick_window(ctx: &egui::Context, table: &Table, info: &RoomInfo, open: &mut bool) {
+    let Some((trick, winner)) = table.tricks.last() else {
+        return;
+    };
+    egui::Window::new("Last trick")
+        .open(open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for play in trick.plays() {
+                    ui.vertical(|ui| {
+                        let (rect, _) =
+                            ui.allocate_exact_size(PEEK_CARD_SIZE, egui::Sense::hover());
+                        paint_card(ui.painter(), rect, &CardVariant::Front(play.card));
+                        let name = seat_name(info, play.seat);
+                        if play.seat == *winner {
+                            ui.strong(name);
+                        } else {
+                            ui.label(name);
+                        }
+                    });
+                }
+            });
+            ui.label(format!("{} took the trick", seat_name(info, *winner)));
+        });
+}
+
+fn card_text(ui: &egui::Ui, card: &Card) -> RichText {
+    let color = match card.suit.color() {
+        SuitColor::Red => RED_SUIT_TEXT_COLOR,
+        SuitColor::Black => ui.visuals().strong_text_color(),
+    };
+    RichText::new(card_label(card)).color(color)
+}
+
+/// Lists the tricks of this hand, as far back as the room rules allow.
+pub fn trick_log_view(ctx: &egui::Context, table: &Table, info: &RoomInfo) {
+    let tricks = table.visible_tricks(info.settings.trick_history);
+    // numbering stays the same when older tricks are hidden
+    let first = table.tricks.len() - tricks.len();
+    egui::SidePanel::left("trick_log").show(ctx, |ui| {
+        ui.heading("Tricks");
+        egui::ScrollArea::vertical()
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for (i, (trick, winner)) in tricks.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}.", first + i + 1));
+                        if let Some(leader) = trick.leader() {
+                            ui.label(format!("{}:", seat_name(info, leader)));
+                        }
+                        for play in trick.plays() {
+                            ui.label(card_text(ui, &play.card));
+                        }
+                        ui.label(format!("→ {}", seat_name(info, *winner)));
+                    });
+                }
+            });
+    });
+}
@@ -0,0 +1,317 @@
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+use crate::card::PBN_SEATS;
+use crate::lobby::CurrentRoom;
+use crate::network::{GameLeaveResult, NetworkCommand, PlayCardRequest, TrickHistory};
+use crate::{GameState, MySystemSets};
+
+use self::animation::{AnimationSettings, Animations, advance_animations, animate_game_events};
+use self::faces::{CardFaceSettings, update_card_faces};
+use self::hand::{HandState, toast_view, track_play_results};
+use self::history::{last_trick_window, trick_log_view};
+use self::record::{GameRecord, record_game_events};
+use self::replay::{add_replay_loader, remove_replay, replay_menu};
+use self::scoreboard::{Scoreboard, hand_summary_window, scoreboard_view};
+use self::settings::settings_window;
+use self::table::{Table, table_view, track_game_events};
+use self::theme::{
+    CardTheme, CardThemeLoader, ThemeIndex, ThemeIndexLoader, Themes, load_theme_index,
+    select_theme,
+};
+
+mod animation;
+mod faces;
+mod hand;
+mod history;
+mod record;
+mod replay;
+mod scoreboard;
+mod settings;
+mod table;
+mod theme;
+
+pub struct IngamePlugin;
+
+impl Plugin for IngamePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AnimationSettings>()
+            .init_resource::<CardFaceSettings>()
+            .init_asset::<CardTheme>()
+            .init_asset::<ThemeIndex>()
+            .init_asset_loader::<CardThemeLoader>()
+            .init_asset_loader::<ThemeIndexLoader>()
+            .add_systems(OnEnter(GameState::LoadingAssets), load_theme_index)
+            .add_systems(
+                Update,
+                (
+                    select_theme.run_if(resource_exists::<Themes>),
+                    update_card_faces,
+                )
+                    .chain()
+                    .in_set(MySystemSets::EguiTop),
+            )
+            .add_systems(OnEnter(GameState::Lobby), reset_table)
+            .add_systems(OnEnter(GameState::RoomMenu), remove_table)
+            .add_systems(
+                Update,
+                (
+                    (advance_animations, track_game_events, animate_game_events)
+                        .chain()
+                        .run_if(resource_exists::<Table>)
+                        .run_if(resource_exists::<Scoreboard>)
+                        .run_if(resource_exists::<Animations>)
+                        .run_if(resource_exists::<CurrentRoom>),
+                    record_game_events
+                        .run_if(resource_exists::<GameRecord>)
+                        .run_if(resource_exists::<CurrentRoom>),
+                    track_play_results
+                        .run_if(resource_exists::<HandState>)
+                        .run_if(resource_exists::<CurrentRoom>),
+                )
+                    .before(MySystemSets::EguiTop),
+            )
+            .add_systems(OnEnter(GameState::Ingame), add_ui_state)
+            .add_systems(
+                Update,
+                (update_ui_state, ingame_menu, settings_window)
+                    .chain()
+                    .run_if(in_state(GameState::Ingame))
+                    .in_set(MySystemSets::Egui),
+            )
+            .add_systems(OnExit(GameState::Ingame), remove_ui_state)
+            .add_systems(OnEnter(GameState::Replay), add_replay_loader)
+            .add_systems(
+                Update,
+                replay_menu
+                    .run_if(in_state(GameState::Replay))
+                    .in_set(MySystemSets::Egui),
+            )
+            .add_systems(OnExit(GameState::Replay), remove_replay);
+    }
+}
+
+#[derive(Debug)]
+#[allow(dead_code)] // rust warns about an unread field, but we use it in the "Debug" impl
+enum IngameStatus {
+    Playing,
+    ConfirmLeave,
+    Leaving,
+    Error(String),
+}
+
+#[derive(Resource)]
+struct UiState {
+    ingame_status: IngameStatus,
+    show_last_trick: bool,
+    show_settings: bool,
+}
+
+impl UiState {
+    fn enable_leave_button(&self) -> bool {
+        matches!(
+            self.ingame_status,
+            IngameStatus::Playing | IngameStatus::Error(_)
+        )
+    }
+
+    fn enable_label(&self) -> bool {
+        matches!(self.ingame_status, IngameStatus::Error(_))
+    }
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            ingame_status: IngameStatus::Playing,
+            show_last_trick: false,
+            show_settings: false,
+        }
+    }
+}
+
+fn add_ui_state(mut commands: Commands) {
+    info!("starting Ingame");
+    commands.init_resource::<UiState>();
+}
+
+fn remove_ui_state(mut commands: Commands) {
+    commands.remove_resource::<UiState>();
+}
+
+/// The hand is dealt while the lobby switches over, so the table has to exist before `Ingame`.
+fn reset_table(mut commands: Commands) {
+    commands.init_resource::<Table>();
+    commands.init_resource::<HandState>();
+    commands.init_resource::<Scoreboard>();
+    commands.init_resource::<Animations>();
+    commands.init_resource::<GameRecord>();
+}
+
+fn remove_table(mut commands: Commands) {
+    commands.remove_resource::<Table>();
+    commands.remove_resource::<HandState>();
+    commands.remove_resource::<Scoreboard>();
+    commands.remove_resource::<Animations>();
+    commands.remove_resource::<GameRecord>();
+}
+
+fn update_ui_state(
+    mut ui_state: ResMut<UiState>,
+    mut game_leave_results: EventReader<GameLeaveResult>,
+) {
+    if let Some(GameLeaveResult(Err(e))) = game_leave_results.read().last() {
+        ui_state.ingame_status = IngameStatus::Error(format!("{e:?}"));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn ingame_menu(
+    mut egui_context: EguiContexts,
+    mut ui_state: ResMut<UiState>,
+    room: Res<CurrentRoom>,
+    table: Res<Table>,
+    mut hand_state: ResMut<HandState>,
+    mut scoreboard: ResMut<Scoreboard>,
+    animations: Res<Animations>,
+    record: Res<GameRecord>,
+    mut event_writer: EventWriter<NetworkCommand>,
+) {
+    let ui_state: &mut UiState = &mut ui_state;
+    let game_in_progress = room.info.as_ref().is_some_and(|info| info.started);
+    egui::TopBottomPanel::top("ingame_panel").show(egui_context.ctx_mut(), |ui| {
+        ui.horizontal(|ui| {
+            match &room.info {
+                Some(info) => {
+                    ui.label(format!("Room: {}", info.name));
+                    if info.spectators > 0 {
+                        ui.label(format!("Spectators: {}", info.spectators));
+                    }
+                }
+                None => {
+                    ui.label(format!("Room: {}", room.room_id));
+                }
+            };
+            if room.is_spectator() {
+                ui.label("(spectating)");
+            } else {
+                ui.checkbox(
+                    &mut hand_state.confirm_double_click,
+                    "Confirm with double click",
+                );
+                ui.checkbox(&mut hand_state.keep_arrangement, "Keep card order")
+                    .on_hover_text("Sort the next hand in the suit order of this one");
+                if ui.button("Sort cards").clicked() {
+                    hand_state.sort();
+                }
+            }
+            let last_trick =
+                ui.add_enabled(!table.tricks.is_empty(), egui::Button::new("Last trick"));
+            if last_trick.clicked() {
+                ui_state.show_last_trick = !ui_state.show_last_trick;
+            }
+            ui.menu_button("Export", |ui| {
+                // the deal can be worked out from the tricks, so only export what the rules show
+                let full_history = room
+                    .info
+                    .as_ref()
+                    .is_some_and(|info| info.settings.trick_history == TrickHistory::Full);
+                let pbn_seats = room
+                    .info
+                    .as_ref()
+                    .is_some_and(|info| info.seats == PBN_SEATS);
+                let hand = ui
+                    .add_enabled(
+                        full_history && pbn_seats && table.dealer.is_some(),
+                        egui::Button::new("Hand as PBN"),
+                    )
+                    .on_hover_text("Copy this hand in PBN to the clipboard");
+                if hand.clicked() {
+                    if let Some(pbn) = table.to_pbn() {
+                        ui.ctx().copy_text(pbn.to_string());
+                    }
+                    ui.close_menu();
+                }
+                // once the game is over nothing is left to work out
+                let game_over = room.info.as_ref().is_some_and(|info| !info.started);
+                let game = ui
+                    .add_enabled(full_history || game_over, egui::Button::new("Game as JSON"))
+                    .on_hover_text("Copy the record of the whole game to the clipboard");
+                if game.clicked() {
+                    match record.to_json() {
+                        Ok(json) => ui.ctx().copy_text(json),
+                        Err(e) => ui_state.ingame_status = IngameStatus::Error(e.to_string()),
+                    }
+                    ui.close_menu();
+                }
+            });
+            if ui.button("Settings").clicked() {
+                ui_state.show_settings = !ui_state.show_settings;
+            }
+            let button = ui.add_enabled(ui_state.enable_leave_button(), egui::Button::new("Leave"));
+            if button.clicked() {
+                if game_in_progress && !room.is_spectator() {
+                    ui_state.ingame_status = IngameStatus::ConfirmLeave;
+                } else {
+                    ui_state.ingame_status = IngameStatus::Leaving;
+                    event_writer.send(NetworkCommand::GameLeave(room.room_id.to_owned()));
+                }
+            }
+            ui.add_visible(
+                ui_state.enable_label(),
+                egui::Label::new(format!("{:?}", ui_state.ingame_status)),
+            );
+        });
+    });
+
+    scoreboard_view(
+        egui_context.ctx_mut(),
+        &scoreboard,
+        &table,
+        room.variant().rules(),
+    );
+    if let Some(info) = &room.info {
+        trick_log_view(egui_context.ctx_mut(), &table, info);
+    }
+    egui::CentralPanel::default().show(egui_context.ctx_mut(), |ui| {
+        if let Some(card) = table_view(ui, &table, &room, &mut hand_state, &animations) {
+            event_writer.send(NetworkCommand::GamePlay(
+                room.room_id.to_owned(),
+                PlayCardRequest { card },
+            ));
+        }
+    });
+    toast_view(egui_context.ctx_mut(), &hand_state);
+    hand_summary_window(egui_context.ctx_mut(), &mut scoreboard);
+    if let Some(info) = &room.info {
+        last_trick_window(
+            egui_context.ctx_mut(),
+            &table,
+            info,
+            &mut ui_state.show_last_trick,
+        );
+    }
+
+    match ui_state.ingame_status {
+        IngameStatus::ConfirmLeave => {
+            egui::Window::new("Leave game").show(egui_context.ctx_mut(), |ui| {
+                ui.label("The game is still in progress. Do you really want to leave?");
+                ui.horizontal(|ui| {
+                    if ui.button("Leave").clicked() {
+                        ui_state.ingame_status = IngameStatus::Leaving;
+                        event_writer.send(NetworkCommand::GameLeave(room.room_id.to_owned()));
+                    }
+                    if ui.button("Cancel").clicked() {
+                        ui_state.ingame_status = IngameStatus::Playing;
+                    }
+                });
+            });
+        }
+        IngameStatus::Leaving => {
+            egui::Window::new("Leaving").show(egui_context.ctx_mut(), |ui| {
+                ui.spinner();
+            });
+        }
+        _ => {}
+    }
+}
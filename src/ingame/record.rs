@@ -0,0 +1,207 @@
+use std::fmt::{Display, Formatter};
+
+use bevy::prelude::*;
+use bevy::utils::SystemTime;
+use serde::{Deserialize, Serialize};
+
+use crate::lobby::CurrentRoom;
+use crate::network::{NetworkEvent, RoomInfo};
+
+/// Bumped whenever a record written by an older client can no longer be read.
+pub const RECORD_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum RecordError {
+    Json(serde_json::Error),
+    Version(u32),
+}
+
+impl Display for RecordError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordError::Json(e) => write!(f, "invalid game record: {e}"),
+            RecordError::Version(version) => write!(
+                f,
+                "game record version {version} is not supported, expected {RECORD_VERSION}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+impl From<serde_json::Error> for RecordError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+/// An event of the room together with the time it arrived.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Seconds since the first recorded event.
+    pub time: f64,
+    pub event: NetworkEvent,
+}
+
+/// Everything the server told us about a match, enough to rebuild every deal, trick and score.
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub version: u32,
+    /// Latest snapshot of the room with its settings and seats.
+    pub room: Option<RoomInfo>,
+    /// Seat of the user who recorded the match, the only hand that is shown from the start.
+    pub seat: Option<u8>,
+    pub events: Vec<RecordedEvent>,
+    /// Unix time in seconds of the first recorded event.
+    #[serde(default)]
+    pub started_at: Option<u64>,
+    #[serde(skip)]
+    started: Option<f64>,
+}
+
+impl Default for GameRecord {
+    fn default() -> Self {
+        Self {
+            version: RECORD_VERSION,
+            room: None,
+            seat: None,
+            events: Vec::new(),
+            started_at: None,
+            started: None,
+        }
+    }
+}
+
+impl GameRecord {
+    pub fn record(&mut self, now: f64, event: &NetworkEvent) {
+        let started = *self.started.get_or_insert(now);
+        self.events.push(RecordedEvent {
+            time: now - started,
+            event: event.clone(),
+        });
+    }
+
+    pub fn to_json(&self) -> Result<String, RecordError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, RecordError> {
+        #[derive(Deserialize)]
+        struct Version {
+            version: u32,
+        }
+        // checked first so newer records fail with a clear error
+        let Version { version } = serde_json::from_str(json)?;
+        if version != RECORD_VERSION {
+            return Err(RecordError::Version(version));
+        }
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+pub fn record_game_events(
+    mut record: ResMut<GameRecord>,
+    room: Res<CurrentRoom>,
+    time: Res<Time<Real>>,
+    mut network_events: EventReader<NetworkEvent>,
+) {
+    if room.is_changed() {
+        record.room.clone_from(&room.info);
+        record.seat = room.me().and_then(|me| me.seat);
+    }
+    for network_event in network_events.read() {
+        if !matches!(network_event, NetworkEvent::Welcome(_)) {
+            if record.events.is_empty() {
+                record.started_at = unix_time();
+            }
+            record.record(time.elapsed_secs_f64(), network_event);
+        }
+    }
+}
+
+fn unix_time() -> Option<u64> {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::network::{CardPlayedEvent, TrickTakenEvent};
+
+    fn record() -> GameRecord {
+        let mut record = GameRecord::default();
+        record.record(
+            10.0,
+            &NetworkEvent::CardPlayed(CardPlayedEvent {
+                seat: 1,
+                card: "AH".parse().unwrap(),
+            }),
+        );
+        record.record(
+            12.5,
+            &NetworkEvent::TrickTaken(TrickTakenEvent { winner: 1 }),
+        );
+        record
+    }
+
+    #[test]
+    fn test_record_times_start_at_zero() {
+        let times: Vec<f64> = record().events.iter().map(|event| event.time).collect();
+        assert_eq!(vec![0.0, 2.5], times);
+    }
+
+    #[test]
+    fn test_record_json() {
+        let expected = json!({
+            "version": 1,
+            "room": null,
+            "seat": null,
+            "started_at": 1700000000,
+            "events": [
+                {"time": 0.0, "event": {"event": "card_played", "data": {
+                    "seat": 1, "card": {"suit": "hearts", "rank": "ace"}
+                }}},
+                {"time": 2.5, "event": {"event": "trick_taken", "data": {"winner": 1}}},
+            ]
+        });
+        let record = GameRecord {
+            started_at: Some(1700000000),
+            ..record()
+        };
+        assert_eq!(expected, serde_json::to_value(record).unwrap());
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let json = record().to_json().unwrap();
+        let loaded = GameRecord::from_json(&json).unwrap();
+        assert_eq!(json, loaded.to_json().unwrap());
+        assert_eq!(2, loaded.events.len());
+    }
+
+    #[test]
+    fn test_record_without_start_time() {
+        let json = json!({"version": 1, "room": null, "seat": null, "events": []}).to_string();
+        assert_eq!(None, GameRecord::from_json(&json).unwrap().started_at);
+    }
+
+    #[test]
+    fn test_record_version() {
+        let json = json!({"version": 2, "events": []}).to_string();
+        assert!(matches!(
+            GameRecord::from_json(&json),
+            Err(RecordError::Version(2))
+        ));
+        assert!(matches!(
+            GameRecord::from_json("{}"),
+            Err(RecordError::Json(_))
+        ));
+    }
+}
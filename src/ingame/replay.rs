@@ -0,0 +1,436 @@
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+use super::animation::Animations;
+use super::hand::HandState;
+use super::history::trick_log_view;
+use super::record::{GameRecord, RecordedEvent};
+use super::scoreboard::{Scoreboard, scoreboard_view};
+use super::table::{Table, table_view};
+use crate::GameState;
+use crate::card::{
+    CardContainer, CardSet, CardVariant, OrderedCards, SortMode, Suit, UnorderedCards,
+};
+use crate::lobby::CurrentRoom;
+use crate::network::NetworkEvent;
+
+/// A point of the game the replay can stop at.
+#[derive(Debug, Clone, PartialEq)]
+struct Step {
+    /// Index of the last event applied to the table.
+    event: usize,
+    label: String,
+}
+
+/// Deals and complete tricks of a record, a trick is shown just before it is taken.
+fn steps(events: &[RecordedEvent]) -> Vec<Step> {
+    let mut steps: Vec<Step> = Vec::new();
+    let mut hand = 0;
+    let mut trick = 0;
+    for (i, recorded) in events.iter().enumerate() {
+        match recorded.event {
+            NetworkEvent::HandDealt(_) => {
+                hand += 1;
+                trick = 0;
+                steps.push(Step {
+                    event: i,
+                    label: format!("Hand {hand}, dealt"),
+                });
+            }
+            NetworkEvent::TrickTaken(_) if i > 0 => {
+                trick += 1;
+                steps.push(Step {
+                    event: i - 1,
+                    label: format!("Hand {hand}, trick {trick}"),
+                });
+            }
+            _ => {}
+        }
+    }
+    // the last trick is collected and the final score is in
+    let last = events.len().checked_sub(1);
+    if let Some(last) = last.filter(|last| steps.last().is_none_or(|step| step.event < *last)) {
+        steps.push(Step {
+            event: last,
+            label: "End".to_string(),
+        });
+    }
+    steps
+}
+
+/// Every card each seat held when the hand on the table at `event` was dealt.
+///
+/// Hidden hands are made up from the cards they play until the next deal.
+fn dealt_hands(events: &[RecordedEvent], event: usize) -> Vec<UnorderedCards> {
+    let is_deal = |recorded: &RecordedEvent| matches!(recorded.event, NetworkEvent::HandDealt(_));
+    let Some(start) = events[..=event].iter().rposition(is_deal) else {
+        return Vec::new();
+    };
+    let end = events[start + 1..]
+        .iter()
+        .position(is_deal)
+        .map_or(events.len(), |end| start + 1 + end);
+    let mut hands: Vec<UnorderedCards> = Vec::new();
+    for recorded in &events[start..end] {
+        match &recorded.event {
+            NetworkEvent::HandDealt(hand_dealt) => {
+                hands = hand_dealt
+                    .hands
+                    .iter()
+                    .map(|hand| {
+                        hand.iter()
+                            .filter_map(|card| match card {
+                                CardVariant::Front(card) => Some(*card),
+                                CardVariant::Back => None,
+                            })
+                            .collect()
+                    })
+                    .collect();
+            }
+            NetworkEvent::CardPlayed(card_played) => {
+                if let Some(hand) = hands.get_mut(card_played.seat as usize) {
+                    hand.add(card_played.card);
+                }
+            }
+            _ => {}
+        }
+    }
+    hands
+}
+
+/// Known cards sorted like our own hand, hidden ones after them.
+fn sorted(hand: &[CardVariant], trump: Option<Suit>) -> Vec<CardVariant> {
+    let mut cards: OrderedCards = hand
+        .iter()
+        .filter_map(|card| match card {
+            CardVariant::Front(card) => Some(*card),
+            CardVariant::Back => None,
+        })
+        .collect();
+    cards.sort_by(SortMode::Trump(trump));
+    let hidden = hand.len() - cards.len() as usize;
+    cards
+        .into_iter()
+        .map(CardVariant::Front)
+        .chain(std::iter::repeat_n(CardVariant::Back, hidden))
+        .collect()
+}
+
+/// A loaded game record and the table at the step it is replayed to.
+#[derive(Resource)]
+pub struct Replay {
+    record: GameRecord,
+    steps: Vec<Step>,
+    step: usize,
+    show_all: bool,
+    /// Has no user, so every hand is drawn without being playable.
+    room: CurrentRoom,
+    table: Table,
+    scoreboard: Scoreboard,
+    hand_state: HandState,
+    animations: Animations,
+}
+
+impl Replay {
+    pub fn new(record: GameRecord) -> Self {
+        let mut room = CurrentRoom::new("replay");
+        room.info.clone_from(&record.room);
+        let mut replay = Self {
+            steps: steps(&record.events),
+            record,
+            step: 0,
+            show_all: false,
+            room,
+            table: default(),
+            scoreboard: default(),
+            hand_state: default(),
+            animations: default(),
+        };
+        replay.go_to(0);
+        replay
+    }
+
+    /// Rebuilds the table and the scores from the start of the record up to `step`.
+    fn go_to(&mut self, step: usize) {
+        self.step = step.min(self.steps.len().saturating_sub(1));
+        self.table = Table::default();
+        self.scoreboard = Scoreboard::default();
+        let Some(event) = self.steps.get(self.step).map(|step| step.event) else {
+            return;
+        };
+        let honours = self
+            .room
+            .info
+            .as_ref()
+            .is_some_and(|info| info.settings.honours);
+        for recorded in &self.record.events[..=event] {
+            if let NetworkEvent::Score(score) = &recorded.event {
+                self.scoreboard
+                    .apply(score, &self.table, self.room.variant().rules(), honours);
+            }
+            self.table.apply(&recorded.event);
+        }
+        if self.show_all {
+            let played: CardSet = self
+                .table
+                .tricks
+                .iter()
+                .map(|(trick, _)| trick)
+                .chain([&self.table.trick])
+                .flat_map(|trick| trick.plays().iter().map(|play| play.card))
+                .collect();
+            for (seat, dealt) in dealt_hands(&self.record.events, event)
+                .into_iter()
+                .enumerate()
+            {
+                if let Some(hand) = self.table.hands.get_mut(seat) {
+                    *hand = dealt
+                        .difference(&played)
+                        .iter()
+                        .map(CardVariant::Front)
+                        .collect();
+                }
+            }
+        }
+        for hand in &mut self.table.hands {
+            *hand = sorted(hand, self.table.trump);
+        }
+    }
+
+    fn label(&self) -> &str {
+        self.steps
+            .get(self.step)
+            .map_or("Nothing recorded", |step| &step.label)
+    }
+}
+
+/// Text of the record the user pasted and why it could not be loaded.
+#[derive(Resource, Debug, Default)]
+pub struct ReplayLoader {
+    json: String,
+    error: Option<String>,
+}
+
+pub fn add_replay_loader(mut commands: Commands) {
+    info!("starting Replay");
+    commands.init_resource::<ReplayLoader>();
+}
+
+pub fn remove_replay(mut commands: Commands) {
+    commands.remove_resource::<ReplayLoader>();
+    commands.remove_resource::<Replay>();
+}
+
+pub fn replay_menu(
+    mut commands: Commands,
+    mut egui_context: EguiContexts,
+    mut state: ResMut<NextState<GameState>>,
+    mut loader: ResMut<ReplayLoader>,
+    replay: Option<ResMut<Replay>>,
+) {
+    let Some(mut replay) = replay else {
+        load_window(&mut commands, &mut egui_context, &mut state, &mut loader);
+        return;
+    };
+    let replay: &mut Replay = &mut replay;
+    let last = replay.steps.len().saturating_sub(1);
+    egui::TopBottomPanel::top("replay_panel").show(egui_context.ctx_mut(), |ui| {
+        ui.horizontal(|ui| {
+            if let Some(info) = &replay.room.info {
+                ui.label(format!("Replay: {}", info.name));
+            }
+            let mut step = replay.step;
+            let (previous, next) = ui.input(|i| {
+                (
+                    i.key_pressed(egui::Key::ArrowLeft),
+                    i.key_pressed(egui::Key::ArrowRight),
+                )
+            });
+            let button = ui.add_enabled(step > 0, egui::Button::new("Previous"));
+            if button.clicked() || previous {
+                step = step.saturating_sub(1);
+            }
+            let button = ui.add_enabled(step < last, egui::Button::new("Next"));
+            if button.clicked() || next {
+                step = (step + 1).min(last);
+            }
+            ui.add_enabled(
+                last > 0,
+                egui::Slider::new(&mut step, 0..=last).show_value(false),
+            );
+            ui.label(replay.label());
+            let show_all = ui
+                .checkbox(&mut replay.show_all, "Show all hands")
+                .changed();
+            if step != replay.step || show_all {
+                replay.go_to(step);
+            }
+            if ui.button("Close").clicked() {
+                commands.remove_resource::<Replay>();
+            }
+        });
+    });
+
+    scoreboard_view(
+        egui_context.ctx_mut(),
+        &replay.scoreboard,
+        &replay.table,
+        replay.room.variant().rules(),
+    );
+    if let Some(info) = &replay.room.info {
+        trick_log_view(egui_context.ctx_mut(), &replay.table, info);
+    }
+    egui::CentralPanel::default().show(egui_context.ctx_mut(), |ui| {
+        if replay.room.info.is_none() {
+            ui.label("The record has no room, so there is no table to show.");
+            return;
+        }
+        // nobody sits at the replayed table, no card can be played
+        table_view(
+            ui,
+            &replay.table,
+            &replay.room,
+            &mut replay.hand_state,
+            &replay.animations,
+        );
+    });
+}
+
+fn load_window(
+    commands: &mut Commands,
+    egui_context: &mut EguiContexts,
+    state: &mut NextState<GameState>,
+    loader: &mut ReplayLoader,
+) {
+    egui::CentralPanel::default().show(egui_context.ctx_mut(), |ui| {
+        ui.heading("Replay");
+        ui.label("Paste a game exported as JSON:");
+        egui::ScrollArea::vertical()
+            .max_height(ui.available_height() - 50.0)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(&mut loader.json)
+                        .code_editor()
+                        .desired_width(f32::INFINITY),
+                );
+            });
+        ui.horizontal(|ui| {
+            let button = ui.add_enabled(!loader.json.is_empty(), egui::Button::new("Load"));
+            if button.clicked() {
+                match GameRecord::from_json(&loader.json) {
+                    Ok(record) => {
+                        loader.error = None;
+                        commands.insert_resource(Replay::new(record));
+                    }
+                    Err(e) => loader.error = Some(e.to_string()),
+                }
+            }
+            if ui.button("Back").clicked() {
+                state.set(GameState::RoomMenu);
+            }
+        });
+        if let Some(error) = &loader.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::card::Card;
+    use crate::network::{CardPlayedEvent, HandDealtEvent, TrickTakenEvent, TurnEvent};
+
+    fn card(s: &str) -> Card {
+        s.parse().unwrap()
+    }
+
+    fn play(seat: u8, card: &str) -> NetworkEvent {
+        NetworkEvent::CardPlayed(CardPlayedEvent {
+            seat,
+            card: self::card(card),
+        })
+    }
+
+    /// Two seats with two cards each, we are seat 0 and play both tricks.
+    fn record() -> GameRecord {
+        let mut record = GameRecord::default();
+        let events = [
+            NetworkEvent::HandDealt(HandDealtEvent {
+                dealer: 1,
+                hands: vec![
+                    vec![
+                        CardVariant::Front(card("AH")),
+                        CardVariant::Front(card("2S")),
+                    ],
+                    vec![CardVariant::Back, CardVariant::Back],
+                ],
+                trump: None,
+                turned_up: None,
+            }),
+            NetworkEvent::Turn(TurnEvent { seat: 0 }),
+            play(0, "AH"),
+            play(1, "3H"),
+            NetworkEvent::TrickTaken(TrickTakenEvent { winner: 0 }),
+            play(0, "2S"),
+            play(1, "KS"),
+            NetworkEvent::TrickTaken(TrickTakenEvent { winner: 1 }),
+        ];
+        for (i, event) in events.iter().enumerate() {
+            record.record(i as f64, event);
+        }
+        record
+    }
+
+    #[test]
+    fn test_steps_show_complete_tricks() {
+        let stops: Vec<(usize, String)> = steps(&record().events)
+            .into_iter()
+            .map(|step| (step.event, step.label))
+            .collect();
+        assert_eq!(
+            vec![
+                (0, "Hand 1, dealt".to_string()),
+                (3, "Hand 1, trick 1".to_string()),
+                (6, "Hand 1, trick 2".to_string()),
+                (7, "End".to_string()),
+            ],
+            stops
+        );
+        assert!(steps(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_dealt_hands_include_hidden_cards() {
+        let hands: Vec<String> = dealt_hands(&record().events, 2)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(vec!["S:2 H:A D:- C:-", "S:K H:3 D:- C:-"], hands);
+    }
+
+    #[test]
+    fn test_go_to_shows_all_hands() {
+        let mut replay = Replay::new(record());
+        assert_eq!("Hand 1, dealt", replay.label());
+        assert_eq!(
+            vec![CardVariant::Back, CardVariant::Back],
+            replay.table.hands[1]
+        );
+
+        replay.show_all = true;
+        replay.go_to(1);
+        assert_eq!(2, replay.table.trick.len());
+        assert_eq!(vec![CardVariant::Front(card("2S"))], replay.table.hands[0]);
+        assert_eq!(vec![CardVariant::Front(card("KS"))], replay.table.hands[1]);
+
+        replay.go_to(10);
+        assert_eq!("End", replay.label());
+        assert_eq!(
+            Some([1, 1]),
+            replay.table.team_tricks(replay.room.variant().rules())
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use super::table::Table;
+use crate::card::{
+    CardContainer, HandScore, Rubber, TEAM_COUNT, TeamValues, UnorderedCards, Variant,
+    honour_points,
+};
+use crate::network::ScoreEvent;
+
+/// Scores of the room as sent by the server, cross-checked with the local scoring rules.
+#[derive(Resource, Debug, Default)]
+pub struct Scoreboard {
+    rubber: Rubber,
+    /// Set once the local rules disagreed with the server.
+    mismatch: bool,
+    /// Hand shown in the end-of-hand summary until it is closed.
+    summary: Option<HandScore>,
+}
+
+/// The hand as counted from the tricks on `table`, `None` unless we saw every trick of it.
+///
+/// Every card has been played by the end of a hand, so the honours each seat held are known.
+fn count_hand(
+    score: &ScoreEvent,
+    table: &Table,
+    rules: &dyn Variant,
+    honours: bool,
+) -> Option<HandScore> {
+    let tricks: u8 = score.hand.tricks.iter().sum();
+    if table.tricks.len() != tricks as usize {
+        return None;
+    }
+    let mut held = vec![UnorderedCards::new(); table.hands.len()];
+    for play in table.tricks.iter().flat_map(|(trick, _)| trick.plays()) {
+        if let Some(hand) = held.get_mut(play.seat as usize) {
+            hand.add(play.card);
+        }
+    }
+    let honour_points = match table.trump {
+        Some(trump) if honours => honour_points(&held, trump),
+        _ => default(),
+    };
+    Some(HandScore::new(table.team_tricks(rules)?, honour_points))
+}
+
+impl Scoreboard {
+    /// Adds the score of the hand that just ended on `table`, checked against the tricks played.
+    pub fn apply(&mut self, score: &ScoreEvent, table: &Table, rules: &dyn Variant, honours: bool) {
+        if self.rubber.is_finished() {
+            // the server carries on with the next rubber
+            self.rubber = Rubber::new();
+        }
+        // the server is authoritative
+        let local = count_hand(score, table, rules, honours);
+        if local.is_some_and(|local| local != score.hand) {
+            warn!(
+                "server hand {:?} differs from local {:?}",
+                score.hand, local
+            );
+            self.mismatch = true;
+        }
+        self.rubber.add_hand(score.hand);
+        if self.rubber.game_points != score.game_points || self.rubber.games != score.games {
+            warn!(
+                "server score {:?} differs from local {:?}",
+                score, self.rubber
+            );
+            self.mismatch = true;
+            self.rubber.game_points = score.game_points;
+            self.rubber.games = score.games;
+        }
+        self.summary = Some(score.hand);
+    }
+}
+
+fn team_row<T: ToString>(ui: &mut egui::Ui, label: &str, values: TeamValues<T>) {
+    ui.label(label);
+    for value in values {
+        ui.label(value.to_string());
+    }
+    ui.end_row();
+}
+
+fn team_header(ui: &mut egui::Ui) {
+    ui.label("");
+    for team in 0..TEAM_COUNT {
+        ui.strong(format!("Team {}", team + 1));
+    }
+    ui.end_row();
+}
+
+pub fn scoreboard_view(
+    ctx: &egui::Context,
+    scoreboard: &Scoreboard,
+    table: &Table,
+    rules: &dyn Variant,
+) {
+    let rubber = &scoreboard.rubber;
+    egui::SidePanel::right("scoreboard").show(ctx, |ui| {
+        ui.heading("Score");
+        egui::Grid::new("score_totals").show(ui, |ui| {
+            team_header(ui);
+            if let Some(tricks) = table.team_tricks(rules) {
+                team_row(ui, "Tricks", tricks);
+            }
+            team_row(ui, "Points", rubber.game_points);
+            team_row(ui, "Games", rubber.games);
+        });
+        if let Some(winner) = rubber.winner() {
+            ui.label(format!("Team {} won the rubber", winner + 1));
+        }
+        if scoreboard.mismatch {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                "The server score differs from the local rules.",
+            );
+        }
+        ui.separator();
+        egui::CollapsingHeader::new("Hands")
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new("score_history")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        team_header(ui);
+                        for (i, hand) in rubber.hands.iter().enumerate() {
+                            let points = hand.points();
+                            ui.label(format!("#{}", i + 1));
+                            for (points, tricks) in points.iter().zip(hand.tricks) {
+                                ui.label(format!("{points} ({tricks})"));
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+    });
+}
+
+/// Summary of the last hand, shown until the user closes it.
+pub fn hand_summary_window(ctx: &egui::Context, scoreboard: &mut Scoreboard) {
+    let Some(hand) = scoreboard.summary else {
+        return;
+    };
+    let rubber = &scoreboard.rubber;
+    let mut close = false;
+    egui::Window::new("Hand finished")
+        .collapsible(false)
+        .show(ctx, |ui| {
+            egui::Grid::new("hand_summary").show(ui, |ui| {
+                team_header(ui);
+                team_row(ui, "Tricks", hand.tricks);
+                team_row(ui, "Book points", hand.trick_points);
+                if hand.honour_points.iter().any(|points| *points > 0) {
+                    team_row(ui, "Honours", hand.honour_points);
+                }
+                team_row(ui, "Total", hand.points());
+                team_row(ui, "Game", rubber.game_points);
+                team_row(ui, "Games", rubber.games);
+            });
+            close = ui.button("Continue").clicked();
+        });
+    if close {
+        scoreboard.summary = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::card::{CardVariant, ClassicWhist, Suit};
+    use crate::network::{CardPlayedEvent, HandDealtEvent, NetworkEvent, TrickTakenEvent};
+
+    /// Two tricks won by seat 0, its team held three honours.
+    fn played_table() -> Table {
+        let mut table = Table::default();
+        table.apply(&NetworkEvent::HandDealt(HandDealtEvent {
+            dealer: 3,
+            hands: vec![vec![CardVariant::Back; 2]; 4],
+            trump: Some(Suit::Hearts),
+            turned_up: None,
+        }));
+        for (cards, winner) in [(["AH", "2S", "3S", "4S"], 0), (["KH", "5S", "QH", "JH"], 0)] {
+            for (seat, card) in cards.iter().enumerate() {
+                table.apply(&NetworkEvent::CardPlayed(CardPlayedEvent {
+                    seat: seat as u8,
+                    card: card.parse().unwrap(),
+                }));
+            }
+            table.apply(&NetworkEvent::TrickTaken(TrickTakenEvent { winner }));
+        }
+        table
+    }
+
+    fn score(hand: HandScore, game_points: TeamValues<u32>) -> ScoreEvent {
+        ScoreEvent {
+            hand,
+            game_points,
+            games: [0, 0],
+        }
+    }
+
+    #[test]
+    fn test_scoreboard_matches_server() {
+        let mut scoreboard = Scoreboard::default();
+        let hand = HandScore::new([2, 0], [2, 0]);
+        scoreboard.apply(&score(hand, [2, 0]), &played_table(), &ClassicWhist, true);
+        assert!(!scoreboard.mismatch);
+        assert_eq!(Some(hand), scoreboard.summary);
+    }
+
+    #[test]
+    fn test_scoreboard_counts_tricks_and_honours_from_the_table() {
+        let mut scoreboard = Scoreboard::default();
+        // honours are off in this room
+        let hand = HandScore::new([2, 0], [2, 0]);
+        scoreboard.apply(&score(hand, [2, 0]), &played_table(), &ClassicWhist, false);
+        assert!(scoreboard.mismatch);
+
+        let mut scoreboard = Scoreboard::default();
+        let hand = HandScore::new([1, 1], [0, 0]);
+        scoreboard.apply(&score(hand, [0, 0]), &played_table(), &ClassicWhist, false);
+        assert!(scoreboard.mismatch);
+        assert_eq!(vec![hand], scoreboard.rubber.hands);
+    }
+
+    #[test]
+    fn test_scoreboard_follows_server_on_mismatch() {
+        let mut scoreboard = Scoreboard::default();
+        let hand = HandScore::new([8, 5], [0, 0]);
+        // the tricks were not seen, only the totals are checked
+        scoreboard.apply(
+            &score(hand, [3, 0]),
+            &Table::default(),
+            &ClassicWhist,
+            false,
+        );
+        assert!(scoreboard.mismatch);
+        assert_eq!([3, 0], scoreboard.rubber.game_points);
+    }
+
+    #[test]
+    fn test_scoreboard_starts_next_rubber() {
+        let mut scoreboard = Scoreboard::default();
+        let win = HandScore::new([13, 0], [0, 0]);
+        for games in [[1, 0], [2, 0]] {
+            let event = ScoreEvent {
+                hand: win,
+                game_points: [0, 0],
+                games,
+            };
+            scoreboard.apply(&event, &Table::default(), &ClassicWhist, false);
+        }
+        assert_eq!(Some(0), scoreboard.rubber.winner());
+
+        let hand = HandScore::new([7, 6], [0, 0]);
+        scoreboard.apply(
+            &score(hand, [1, 0]),
+            &Table::default(),
+            &ClassicWhist,
+            false,
+        );
+        assert!(!scoreboard.mismatch);
+        assert_eq!(vec![hand], scoreboard.rubber.hands);
+        assert_eq!([0, 0], scoreboard.rubber.games);
+    }
+}
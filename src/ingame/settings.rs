@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+use super::UiState;
+use super::animation::{AnimationSettings, AnimationSpeed};
+use super::faces::{CardBack, CardFaceSettings};
+use super::theme::{CardTheme, ThemeIndex, Themes};
+
+#[allow(clippy::too_many_arguments)]
+pub fn settings_window(
+    mut egui_context: EguiContexts,
+    mut ui_state: ResMut<UiState>,
+    mut animation_settings: ResMut<AnimationSettings>,
+    mut card_face_settings: ResMut<CardFaceSettings>,
+    themes: Option<Res<Themes>>,
+    theme_indexes: Res<Assets<ThemeIndex>>,
+    theme_assets: Res<Assets<CardTheme>>,
+    images: Res<Assets<Image>>,
+    server: Res<AssetServer>,
+) {
+    let mut speed = animation_settings.speed;
+    let mut faces = card_face_settings.clone();
+    let available = themes
+        .as_ref()
+        .map(|themes| themes.available(&theme_indexes))
+        .unwrap_or_default();
+    egui::Window::new("Settings")
+        .open(&mut ui_state.show_settings)
+        .collapsible(false)
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.label("Animations:");
+            ui.horizontal(|ui| {
+                for option in AnimationSpeed::ALL {
+                    ui.radio_value(&mut speed, option, option.to_string());
+                }
+            });
+            ui.separator();
+            ui.label("Cards:");
+            ui.radio_value(&mut faces.theme, None, "Built-in");
+            for theme in available {
+                ui.radio_value(&mut faces.theme, Some(theme.to_owned()), theme);
+            }
+            if faces.theme.is_none() {
+                ui.horizontal(|ui| {
+                    for back in CardBack::ALL {
+                        ui.radio_value(&mut faces.back, back, back.to_string());
+                    }
+                });
+            }
+            let error = themes
+                .as_ref()
+                .and_then(|themes| themes.error(&server, &theme_assets, &images));
+            if let Some(e) = error {
+                ui.colored_label(
+                    ui.visuals().error_fg_color,
+                    format!("The theme could not be loaded: {e}"),
+                );
+            }
+        });
+    animation_settings.set_if_neq(AnimationSettings { speed });
+    card_face_settings.set_if_neq(faces);
+}
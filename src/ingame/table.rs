@@ -0,0 +1,635 @@
+use bevy::prelude::*;
+use bevy_egui::egui::{self, Align2, Color32, FontId, Painter, Pos2, Rect, Ui, Vec2, vec2};
+
+use super::animation::{Animations, Progress};
+use super::faces::CardAtlas;
+use super::hand::{HandState, hand_view};
+use super::scoreboard::Scoreboard;
+use crate::card::{
+    Card, CardContainer, CardVariant, PBN_SEATS, PbnGame, Suit, SuitColor, TeamValues, Trick,
+    UnorderedCards, Variant,
+};
+use crate::lobby::CurrentRoom;
+use crate::network::{NetworkEvent, RoomInfo, TrickHistory};
+
+const TABLE_COLOR: Color32 = Color32::from_rgb(0x1f, 0x5f, 0x3a);
+const CARD_BACK_COLOR: Color32 = Color32::from_rgb(0x2a, 0x4a, 0x9a);
+const RED_SUIT_COLOR: Color32 = Color32::from_rgb(0xc0, 0x10, 0x10);
+const INDICATOR_COLOR: Color32 = Color32::from_rgb(0x14, 0x3d, 0x26);
+const TURN_COLOR: Color32 = Color32::from_rgb(0xf0, 0xc0, 0x30);
+const MARGIN: f32 = 6.0;
+/// Horizontal distance between the cards of our own hand, relative to the card width.
+const OWN_CARD_SPACING: f32 = 0.55;
+/// Distance between the hidden cards of the other hands, relative to the card size.
+const OTHER_CARD_SPACING: f32 = 0.2;
+/// Size of a collected trick when it reaches the winner.
+const COLLECTED_CARD_SCALE: f32 = 0.5;
+
+/// Where a seat is drawn, the local player always sits at the bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TablePosition {
+    Bottom,
+    Left,
+    Top,
+    Right,
+}
+
+impl TablePosition {
+    /// Returns the position of `seat` if `bottom_seat` is drawn at the bottom.
+    ///
+    /// Play goes clockwise, so the next seat after the bottom one sits on the left.
+    pub fn of_seat(seat: u8, bottom_seat: u8, seats: u8) -> Self {
+        let relative = (seat % seats + seats - bottom_seat % seats) % seats;
+        match (seats, relative) {
+            (_, 0) => TablePosition::Bottom,
+            (2, _) => TablePosition::Top,
+            (3, 1) => TablePosition::Left,
+            (3, _) => TablePosition::Right,
+            (_, 1) => TablePosition::Left,
+            (_, 2) => TablePosition::Top,
+            _ => TablePosition::Right,
+        }
+    }
+
+    /// Unit vector pointing from the centre of the table towards the seat.
+    pub fn direction(&self) -> Vec2 {
+        match self {
+            TablePosition::Bottom => Vec2::DOWN,
+            TablePosition::Left => Vec2::LEFT,
+            TablePosition::Top => Vec2::UP,
+            TablePosition::Right => Vec2::RIGHT,
+        }
+    }
+
+    fn is_horizontal(&self) -> bool {
+        matches!(self, TablePosition::Bottom | TablePosition::Top)
+    }
+}
+
+/// The table as far as we can see it, built from the game events of the room.
+#[derive(Resource, Debug, Default)]
+pub struct Table {
+    pub dealer: Option<u8>,
+    pub trump: Option<Suit>,
+    pub turned_up: Option<Card>,
+    pub turn: Option<u8>,
+    /// Hands indexed by seat.
+    pub hands: Vec<Vec<CardVariant>>,
+    pub trick: Trick,
+    /// Completed tricks of this hand together with the seat that took them.
+    pub tricks: Vec<(Trick, u8)>,
+}
+
+impl Table {
+    pub fn apply(&mut self, event: &NetworkEvent) {
+        match event {
+            NetworkEvent::HandDealt(hand_dealt) => {
+                self.dealer = Some(hand_dealt.dealer);
+                self.trump = hand_dealt.trump;
+                self.turned_up = hand_dealt.turned_up;
+                // nobody is on turn until the server says so
+                self.turn = None;
+                self.hands.clone_from(&hand_dealt.hands);
+                self.trick = Trick::new();
+                self.tricks.clear();
+            }
+            NetworkEvent::Turn(turn) => {
+                self.turn = Some(turn.seat);
+            }
+            NetworkEvent::CardPlayed(card_played) => {
+                self.remove_from_hand(card_played.seat, card_played.card);
+                self.trick.play(card_played.seat, card_played.card);
+            }
+            NetworkEvent::TrickTaken(trick_taken) => {
+                let trick = std::mem::take(&mut self.trick);
+                self.tricks.push((trick, trick_taken.winner));
+            }
+            _ => {}
+        }
+    }
+
+    /// Tricks taken by each team in this hand, `None` if `rules` has no partnerships.
+    pub fn team_tricks(&self, rules: &dyn Variant) -> Option<TeamValues<u8>> {
+        // partnerships are fixed for the whole table, so the first seat tells
+        rules.team(0)?;
+        let mut counts = TeamValues::default();
+        for (_, winner) in &self.tricks {
+            counts[rules.team(*winner)? as usize] += 1;
+        }
+        Some(counts)
+    }
+
+    /// Completed tricks the room rules let us look at.
+    pub fn visible_tricks(&self, history: TrickHistory) -> &[(Trick, u8)] {
+        match history {
+            TrickHistory::Full => &self.tricks,
+            TrickHistory::LastTrick => {
+                let start = self.tricks.len().saturating_sub(1);
+                &self.tricks[start..]
+            }
+        }
+    }
+
+    /// The hand so far in PBN, with as much of the deal as we have seen.
+    ///
+    /// Hands that are not fully known yet are left empty, they show as `-`.
+    pub fn to_pbn(&self) -> Option<PbnGame> {
+        if self.hands.len() != PBN_SEATS as usize {
+            return None;
+        }
+        let mut game = PbnGame {
+            dealer: self.dealer?,
+            trump: self.trump,
+            ..default()
+        };
+        for (seat, hand) in self.hands.iter().enumerate() {
+            game.hands[seat] = hand
+                .iter()
+                .filter_map(|card| match card {
+                    CardVariant::Front(card) => Some(*card),
+                    CardVariant::Back => None,
+                })
+                .collect();
+        }
+        let current = Some(&self.trick).filter(|trick| !trick.is_empty());
+        for trick in self.tricks.iter().map(|(trick, _)| trick).chain(current) {
+            for play in trick.plays() {
+                game.hands[play.seat as usize].add(play.card);
+            }
+            game.tricks.push(trick.clone());
+        }
+        // a card back still in the hand means some of its cards are unknown
+        for (seat, hand) in self.hands.iter().enumerate() {
+            if hand.contains(&CardVariant::Back) {
+                game.hands[seat] = default();
+            }
+        }
+        Some(game)
+    }
+
+    /// Removes a played card, for hidden hands any card back is removed instead.
+    fn remove_from_hand(&mut self, seat: u8, card: Card) {
+        let Some(hand) = self.hands.get_mut(seat as usize) else {
+            return;
+        };
+        let position = hand
+            .iter()
+            .position(|c| *c == CardVariant::Front(card))
+            .or_else(|| hand.iter().position(|c| *c == CardVariant::Back));
+        if let Some(position) = position {
+            hand.remove(position);
+        }
+    }
+}
+
+/// Scores are checked against the finished hand, before the next deal clears the table.
+pub fn track_game_events(
+    mut table: ResMut<Table>,
+    mut scoreboard: ResMut<Scoreboard>,
+    room: Res<CurrentRoom>,
+    mut network_events: EventReader<NetworkEvent>,
+) {
+    let honours = room.info.as_ref().is_some_and(|info| info.settings.honours);
+    let rules = room.variant().rules();
+    for network_event in network_events.read() {
+        if let NetworkEvent::Score(score) = network_event {
+            scoreboard.apply(score, &table, rules, honours);
+        }
+        table.apply(network_event);
+    }
+}
+
+/// Size of a card, scaled with the table so everything fits at any window size.
+pub fn card_size(table_rect: Rect) -> Vec2 {
+    let height = (table_rect.height().min(table_rect.width()) * 0.16).clamp(24.0, 120.0);
+    vec2(height * 0.7, height)
+}
+
+/// Centre of the hand of a seat at `position`, close to the edge of the table.
+pub fn hand_center(table_rect: Rect, position: TablePosition, card_size: Vec2) -> Pos2 {
+    let half_extent = if position.is_horizontal() {
+        table_rect.height() / 2.0 - card_size.y / 2.0
+    } else {
+        table_rect.width() / 2.0 - card_size.x / 2.0
+    };
+    table_rect.center() + position.direction() * (half_extent - MARGIN)
+}
+
+/// Returns the rects of the cards of a hand, from the first to the last card.
+pub fn hand_card_rects(
+    table_rect: Rect,
+    position: TablePosition,
+    card_size: Vec2,
+    count: usize,
+) -> Vec<Rect> {
+    let center = hand_center(table_rect, position, card_size);
+    let (axis, spacing) = match position {
+        TablePosition::Bottom => (Vec2::RIGHT, card_size.x * OWN_CARD_SPACING),
+        TablePosition::Top => (Vec2::RIGHT, card_size.x * OTHER_CARD_SPACING),
+        TablePosition::Left | TablePosition::Right => {
+            (Vec2::DOWN, card_size.y * OTHER_CARD_SPACING)
+        }
+    };
+    let length = spacing * count.saturating_sub(1) as f32;
+    (0..count)
+        .map(|i| {
+            let offset = i as f32 * spacing - length / 2.0;
+            Rect::from_center_size(center + axis * offset, card_size)
+        })
+        .collect()
+}
+
+/// Rect of the card a seat played into the current trick.
+pub fn trick_card_rect(table_rect: Rect, position: TablePosition, card_size: Vec2) -> Rect {
+    let offset = position.direction() * card_size * 0.6;
+    Rect::from_center_size(table_rect.center() + offset, card_size)
+}
+
+pub fn suit_color(suit: Suit) -> Color32 {
+    match suit.color() {
+        SuitColor::Red => RED_SUIT_COLOR,
+        SuitColor::Black => Color32::BLACK,
+    }
+}
+
+/// Short text of a card like "T♥", for lists where a painted card is too big.
+pub fn card_label(card: &Card) -> String {
+    format!("{}{}", card.rank.letter(), card.suit.symbol())
+}
+
+pub fn seat_name(info: &RoomInfo, seat: u8) -> String {
+    match info.seat_holder(seat) {
+        Some(player) => player.username.to_owned(),
+        None => format!("Seat {}", seat + 1),
+    }
+}
+
+pub fn paint_card(painter: &Painter, rect: Rect, card: &CardVariant) {
+    if let Some(atlas) = CardAtlas::get(painter.ctx()) {
+        let (texture, uv) = atlas.image(card);
+        painter.image(texture, rect, uv, Color32::WHITE);
+        return;
+    }
+    let rounding = rect.width() * 0.08;
+    painter.rect_filled(rect, rounding, Color32::DARK_GRAY);
+    let inner = rect.shrink(1.0);
+    match card {
+        CardVariant::Back => {
+            painter.rect_filled(inner, rounding, CARD_BACK_COLOR);
+        }
+        CardVariant::Front(card) => {
+            let color = suit_color(card.suit);
+            painter.rect_filled(inner, rounding, Color32::WHITE);
+            painter.text(
+                inner.left_top() + vec2(2.0, 1.0),
+                Align2::LEFT_TOP,
+                card.rank.letter(),
+                FontId::proportional(rect.height() * 0.26),
+                color,
+            );
+            painter.text(
+                inner.center(),
+                Align2::CENTER_CENTER,
+                card.suit.symbol(),
+                FontId::proportional(rect.height() * 0.4),
+                color,
+            );
+        }
+    }
+}
+
+fn paint_seat_label(painter: &Painter, pos: Pos2, text: String, highlighted: bool) {
+    let text_color = if highlighted {
+        Color32::BLACK
+    } else {
+        Color32::WHITE
+    };
+    let galley = painter.layout_no_wrap(text, FontId::proportional(12.0), text_color);
+    let rect = Rect::from_center_size(pos, galley.size());
+    if highlighted {
+        painter.rect_filled(rect.expand(2.0), 3.0, TURN_COLOR);
+    }
+    painter.galley(rect.min, galley, text_color);
+}
+
+/// Shows the trump suit in the top left corner of the table, next to the turned up card.
+fn paint_trump_indicator(painter: &Painter, table_rect: Rect, card_size: Vec2, table: &Table) {
+    let rect = Rect::from_min_size(
+        table_rect.left_top() + vec2(MARGIN, MARGIN),
+        vec2(card_size.x * 1.4, card_size.y),
+    );
+    painter.rect_filled(rect, 4.0, INDICATOR_COLOR);
+    painter.text(
+        rect.center_top() + vec2(0.0, 2.0),
+        Align2::CENTER_TOP,
+        "Trump",
+        FontId::proportional(12.0),
+        Color32::WHITE,
+    );
+    let (symbol, color) = match table.trump {
+        Some(trump) => (trump.symbol().to_string(), suit_color(trump)),
+        None => ("–".to_string(), Color32::DARK_GRAY),
+    };
+    let center = rect.center() + vec2(0.0, 6.0);
+    painter.circle_filled(center, card_size.y * 0.3, Color32::WHITE);
+    painter.text(
+        center,
+        Align2::CENTER_CENTER,
+        symbol,
+        FontId::proportional(card_size.y * 0.45),
+        color,
+    );
+    if let Some(card) = table.turned_up {
+        let card_rect = Rect::from_min_size(rect.right_top() + vec2(MARGIN, 0.0), card_size);
+        paint_card(painter, card_rect, &CardVariant::Front(card));
+    }
+}
+
+/// Draws the table with every seat around it and the cards of the current trick.
+///
+/// Returns the card the user chose to play from their own hand.
+pub fn table_view(
+    ui: &mut Ui,
+    table: &Table,
+    room: &CurrentRoom,
+    hand_state: &mut HandState,
+    animations: &Animations,
+) -> Option<Card> {
+    let info = room.info.as_ref()?;
+    let seats = info.seats;
+    if seats == 0 {
+        return None;
+    }
+    let my_seat = room.me().and_then(|me| me.seat);
+    let bottom_seat = my_seat.unwrap_or(0);
+    let mut played = None;
+
+    let (response, painter) = ui.allocate_painter(ui.available_size(), egui::Sense::hover());
+    let rect = response.rect;
+    let card_size = card_size(rect);
+    let felt = CardAtlas::get(ui.ctx()).and_then(|atlas| atlas.felt);
+    painter.rect_filled(rect, 0.0, felt.unwrap_or(TABLE_COLOR));
+    if table.dealer.is_some() {
+        paint_trump_indicator(&painter, rect, card_size, table);
+    }
+    let dealer_rect = table.dealer.map(|dealer| {
+        let position = TablePosition::of_seat(dealer, bottom_seat, seats);
+        Rect::from_center_size(hand_center(rect, position, card_size), card_size)
+    });
+    // the own hand can only be played once it is dealt
+    let dealing = animations.is_dealing();
+
+    for seat in 0..seats {
+        let position = TablePosition::of_seat(seat, bottom_seat, seats);
+        let hand = table
+            .hands
+            .get(seat as usize)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if my_seat == Some(seat) && !dealing {
+            let cards: UnorderedCards = hand
+                .iter()
+                .filter_map(|card| match card {
+                    CardVariant::Front(card) => Some(*card),
+                    CardVariant::Back => None,
+                })
+                .collect();
+            let playable = (table.turn == Some(seat) && hand_state.pending().is_none())
+                .then(|| cards.playable(table.trick.led_suit()));
+            let cards = hand_state.arrange(&cards, table.trump);
+            played = hand_view(
+                ui,
+                &painter,
+                rect,
+                card_size,
+                &cards,
+                playable.as_ref(),
+                hand_state,
+            )
+            .or(played);
+        } else {
+            let mut hand = hand.to_vec();
+            if my_seat == Some(seat) {
+                let cards: UnorderedCards = hand
+                    .iter()
+                    .filter_map(|card| match card {
+                        CardVariant::Front(card) => Some(*card),
+                        CardVariant::Back => None,
+                    })
+                    .collect();
+                hand = hand_state
+                    .arrange(&cards, table.trump)
+                    .into_iter()
+                    .map(CardVariant::Front)
+                    .collect();
+            }
+            for (index, (card_rect, card)) in hand_card_rects(rect, position, card_size, hand.len())
+                .into_iter()
+                .zip(&hand)
+                .enumerate()
+            {
+                let card_rect = match (animations.deal(seat, index), dealer_rect) {
+                    (Some(Progress::Waiting), _) => continue,
+                    (Some(progress), Some(dealer_rect)) => progress.lerp(dealer_rect, card_rect),
+                    _ => card_rect,
+                };
+                paint_card(&painter, card_rect, card);
+            }
+        }
+
+        let mut name = seat_name(info, seat);
+        if table.dealer == Some(seat) {
+            name.push_str(" (D)");
+        }
+        let label_pos = hand_center(rect, position, card_size)
+            - position.direction() * (card_size.y / 2.0 + 10.0);
+        paint_seat_label(&painter, label_pos, name, table.turn == Some(seat));
+
+        let trick_card = table.trick.card_of(seat).or_else(|| {
+            if my_seat == Some(seat) {
+                hand_state.pending()
+            } else {
+                None
+            }
+        });
+        if let Some(card) = trick_card {
+            let mut card_rect = trick_card_rect(rect, position, card_size);
+            if let Some(progress) = animations.play(seat) {
+                let hand_rect =
+                    Rect::from_center_size(hand_center(rect, position, card_size), card_size);
+                card_rect = progress.lerp(hand_rect, card_rect);
+            }
+            paint_card(&painter, card_rect, &CardVariant::Front(card));
+        }
+    }
+
+    if let Some((progress, trick, winner)) = animations.collect() {
+        let winner_position = TablePosition::of_seat(winner, bottom_seat, seats);
+        let winner_rect = Rect::from_center_size(
+            hand_center(rect, winner_position, card_size),
+            card_size * COLLECTED_CARD_SCALE,
+        );
+        for play in trick.plays() {
+            let position = TablePosition::of_seat(play.seat, bottom_seat, seats);
+            let card_rect = progress.lerp(trick_card_rect(rect, position, card_size), winner_rect);
+            paint_card(&painter, card_rect, &CardVariant::Front(play.card));
+        }
+    }
+    played
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::card::{ClassicWhist, OhHell, Rank};
+    use crate::network::{CardPlayedEvent, HandDealtEvent, TrickTakenEvent, TurnEvent};
+
+    const ACE_OF_HEARTS: Card = Card {
+        suit: Suit::Hearts,
+        rank: Rank::Ace,
+    };
+
+    #[test]
+    fn test_table_position_four_seats() {
+        let positions: Vec<TablePosition> = (0..4)
+            .map(|seat| TablePosition::of_seat(seat, 2, 4))
+            .collect();
+        assert_eq!(
+            vec![
+                TablePosition::Top,
+                TablePosition::Right,
+                TablePosition::Bottom,
+                TablePosition::Left
+            ],
+            positions
+        );
+    }
+
+    #[test]
+    fn test_table_position_two_seats() {
+        assert_eq!(TablePosition::Bottom, TablePosition::of_seat(1, 1, 2));
+        assert_eq!(TablePosition::Top, TablePosition::of_seat(0, 1, 2));
+    }
+
+    #[test]
+    fn test_table_position_out_of_range_seat() {
+        assert_eq!(TablePosition::Left, TablePosition::of_seat(5, 4, 4));
+    }
+
+    #[test]
+    fn test_table_card_played() {
+        let mut table = Table::default();
+        table.apply(&NetworkEvent::HandDealt(HandDealtEvent {
+            dealer: 0,
+            hands: vec![
+                vec![CardVariant::Front(ACE_OF_HEARTS)],
+                vec![CardVariant::Back, CardVariant::Back],
+            ],
+            trump: Some(Suit::Spades),
+            turned_up: None,
+        }));
+        table.apply(&NetworkEvent::CardPlayed(CardPlayedEvent {
+            seat: 0,
+            card: ACE_OF_HEARTS,
+        }));
+        table.apply(&NetworkEvent::CardPlayed(CardPlayedEvent {
+            seat: 1,
+            card: Card {
+                suit: Suit::Hearts,
+                rank: Rank::Num2,
+            },
+        }));
+        assert_eq!(Vec::<CardVariant>::new(), table.hands[0]);
+        assert_eq!(vec![CardVariant::Back], table.hands[1]);
+        assert_eq!(Some(ACE_OF_HEARTS), table.trick.card_of(0));
+
+        table.apply(&NetworkEvent::TrickTaken(TrickTakenEvent { winner: 0 }));
+        assert_eq!(None, table.trick.card_of(0));
+        assert_eq!(Some([1, 0]), table.team_tricks(&ClassicWhist));
+        assert_eq!(None, table.team_tricks(&OhHell));
+        assert_eq!(1, table.tricks.len());
+        assert_eq!(Some(0), table.tricks[0].0.leader());
+        assert_eq!(Some(Suit::Spades), table.trump);
+    }
+
+    #[test]
+    fn test_table_hand_dealt_clears_turn() {
+        let mut table = Table::default();
+        table.apply(&NetworkEvent::Turn(TurnEvent { seat: 2 }));
+        assert_eq!(Some(2), table.turn);
+        table.apply(&NetworkEvent::HandDealt(HandDealtEvent {
+            dealer: 2,
+            hands: vec![vec![CardVariant::Back]; 4],
+            trump: None,
+            turned_up: None,
+        }));
+        assert_eq!(None, table.turn);
+    }
+
+    #[test]
+    fn test_hand_card_rects_centered() {
+        let table_rect = Rect::from_min_size(Pos2::ZERO, vec2(400.0, 300.0));
+        let size = card_size(table_rect);
+        let rects = hand_card_rects(table_rect, TablePosition::Bottom, size, 3);
+        assert_eq!(3, rects.len());
+        assert_eq!(table_rect.center().x, rects[1].center().x);
+        assert!(rects[2].bottom() <= table_rect.bottom());
+    }
+
+    #[test]
+    fn test_table_visible_tricks() {
+        let mut table = Table::default();
+        for winner in [2, 3] {
+            table.apply(&NetworkEvent::CardPlayed(CardPlayedEvent {
+                seat: 0,
+                card: ACE_OF_HEARTS,
+            }));
+            table.apply(&NetworkEvent::TrickTaken(TrickTakenEvent { winner }));
+        }
+        assert_eq!(2, table.visible_tricks(TrickHistory::Full).len());
+        let last = table.visible_tricks(TrickHistory::LastTrick);
+        assert_eq!(1, last.len());
+        assert_eq!(3, last[0].1);
+        assert!(
+            Table::default()
+                .visible_tricks(TrickHistory::LastTrick)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_table_to_pbn() {
+        let mut table = Table::default();
+        assert_eq!(None, table.to_pbn());
+        table.apply(&NetworkEvent::HandDealt(HandDealtEvent {
+            dealer: 3,
+            hands: vec![
+                vec![CardVariant::Front(ACE_OF_HEARTS)],
+                vec![CardVariant::Back, CardVariant::Back],
+                vec![CardVariant::Back],
+                vec![CardVariant::Back],
+            ],
+            trump: None,
+            turned_up: None,
+        }));
+        table.apply(&NetworkEvent::CardPlayed(CardPlayedEvent {
+            seat: 0,
+            card: ACE_OF_HEARTS,
+        }));
+        table.apply(&NetworkEvent::CardPlayed(CardPlayedEvent {
+            seat: 1,
+            card: "2H".parse().unwrap(),
+        }));
+        assert_eq!(
+            "[Dealer \"W\"]\n\
+             [Deal \"W:- .A.. - -\"]\n\
+             [Trump \"NT\"]\n\
+             [Play \"N\"]\n\
+             HA H2 - -\n\
+             *\n",
+            table.to_pbn().unwrap().to_string()
+        );
+    }
+}
@@ -0,0 +1,410 @@
+use std::fmt::{Display, Formatter};
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, LoadState, RecursiveDependencyLoadState};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::faces::{CardFaceSettings, FACES};
+use crate::assets::Preload;
+use crate::card::Card;
+
+/// Lists the directories below `assets/themes`, browsers can not list folders themselves.
+pub const THEME_INDEX_PATH: &str = "themes/index.json";
+
+pub fn theme_path(directory: &str) -> String {
+    format!("themes/{directory}/theme.json")
+}
+
+#[derive(Debug)]
+pub enum ThemeError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    MissingCards(Vec<Card>),
+    DuplicateCard(Card),
+    EmptyRect(Card),
+    /// The rect reaches past the largest possible image.
+    RectOverflow(Card),
+    /// Rects which do not fit into the atlas image of the given size.
+    OutsideAtlas(Vec<Card>, UVec2),
+}
+
+impl Display for ThemeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeError::Io(e) => write!(f, "could not read theme: {e}"),
+            ThemeError::Json(e) => write!(f, "invalid theme manifest: {e}"),
+            ThemeError::MissingCards(cards) => {
+                let cards: Vec<String> = cards.iter().map(Card::to_string).collect();
+                write!(f, "theme has no image for {}", cards.join(", "))
+            }
+            ThemeError::DuplicateCard(card) => write!(f, "theme lists the {card} twice"),
+            ThemeError::EmptyRect(card) => write!(f, "theme has an empty rect for the {card}"),
+            ThemeError::RectOverflow(card) => write!(f, "theme rect of the {card} is too large"),
+            ThemeError::OutsideAtlas(cards, size) => {
+                let cards: Vec<String> = cards.iter().map(Card::to_string).collect();
+                write!(
+                    f,
+                    "theme rects of {} do not fit into the {}x{} atlas",
+                    cards.join(", "),
+                    size.x,
+                    size.y
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+impl From<std::io::Error> for ThemeError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for ThemeError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+struct CardRect {
+    card: Card,
+    /// Left, top, width and height in pixels of the atlas.
+    rect: [u32; 4],
+}
+
+impl CardRect {
+    fn urect(&self) -> Result<URect, ThemeError> {
+        let [x, y, width, height] = self.rect;
+        match (x.checked_add(width), y.checked_add(height)) {
+            (Some(right), Some(bottom)) => Ok(URect::new(x, y, right, bottom)),
+            _ => Err(ThemeError::RectOverflow(self.card)),
+        }
+    }
+}
+
+/// Manifest of a theme as written by designers, image paths are relative to it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct ThemeManifest {
+    name: String,
+    atlas: String,
+    cards: Vec<CardRect>,
+    back: String,
+    #[serde(default)]
+    felt: Option<[u8; 3]>,
+}
+
+impl ThemeManifest {
+    fn parse(bytes: &[u8]) -> Result<Self, ThemeError> {
+        let manifest: Self = serde_json::from_slice(bytes)?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    fn validate(&self) -> Result<(), ThemeError> {
+        let mut seen = [false; FACES];
+        for card_rect @ CardRect { card, rect } in &self.cards {
+            let index: u8 = (*card).into();
+            if std::mem::replace(&mut seen[index as usize], true) {
+                return Err(ThemeError::DuplicateCard(*card));
+            }
+            if rect[2] == 0 || rect[3] == 0 {
+                return Err(ThemeError::EmptyRect(*card));
+            }
+            card_rect.urect()?;
+        }
+        let missing: Vec<Card> = (0..FACES as u8)
+            .filter(|index| !seen[*index as usize])
+            .filter_map(|index| Card::try_from(index).ok())
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(ThemeError::MissingCards(missing))
+        }
+    }
+}
+
+/// Card images from the assets folder, used instead of the built-in faces.
+#[derive(Asset, TypePath, Debug)]
+pub struct CardTheme {
+    #[dependency]
+    pub atlas: Handle<Image>,
+    /// Pixel rects in the atlas, indexed like the cards.
+    pub cards: Vec<URect>,
+    #[dependency]
+    pub back: Handle<Image>,
+    /// Colour of the table.
+    pub felt: Option<[u8; 3]>,
+}
+
+impl CardTheme {
+    /// The manifest is loaded before the atlas, so its rects are checked once the image is known.
+    pub fn check_atlas(&self, size: UVec2) -> Result<(), ThemeError> {
+        let outside: Vec<Card> = self
+            .cards
+            .iter()
+            .enumerate()
+            .filter(|(_, rect)| rect.max.x > size.x || rect.max.y > size.y)
+            .filter_map(|(index, _)| Card::try_from(index as u8).ok())
+            .collect();
+        if outside.is_empty() {
+            Ok(())
+        } else {
+            Err(ThemeError::OutsideAtlas(outside, size))
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CardThemeLoader;
+
+impl AssetLoader for CardThemeLoader {
+    type Asset = CardTheme;
+    type Settings = ();
+    type Error = ThemeError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let manifest = ThemeManifest::parse(&bytes)?;
+        let directory = load_context
+            .path()
+            .parent()
+            .map(|path| path.to_path_buf())
+            .unwrap_or_default();
+        let mut cards = vec![URect::default(); FACES];
+        for card_rect in manifest.cards {
+            let index: u8 = card_rect.card.into();
+            cards[index as usize] = card_rect.urect()?;
+        }
+        info!("loaded card theme {}", manifest.name);
+        Ok(CardTheme {
+            atlas: load_context.load(directory.join(manifest.atlas)),
+            cards,
+            back: load_context.load(directory.join(manifest.back)),
+            felt: manifest.felt,
+        })
+    }
+}
+
+#[derive(Asset, TypePath, Debug, Default, Deserialize)]
+pub struct ThemeIndex {
+    /// Directory names below `assets/themes`.
+    pub themes: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct ThemeIndexLoader;
+
+impl AssetLoader for ThemeIndexLoader {
+    type Asset = ThemeIndex;
+    type Settings = ();
+    type Error = ThemeError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
+/// Handles of the theme index and the selected theme, kept so they stay loaded.
+#[derive(Resource, Debug, Default)]
+pub struct Themes {
+    index: Handle<ThemeIndex>,
+    selected: Option<Handle<CardTheme>>,
+}
+
+impl Themes {
+    pub fn available<'a>(&self, indexes: &'a Assets<ThemeIndex>) -> &'a [String] {
+        indexes
+            .get(&self.index)
+            .map(|index| index.themes.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn selected(&self) -> Option<&Handle<CardTheme>> {
+        self.selected.as_ref()
+    }
+
+    /// Why the selected theme is not shown, if it failed to load or does not fit its atlas.
+    pub fn error(
+        &self,
+        server: &AssetServer,
+        themes: &Assets<CardTheme>,
+        images: &Assets<Image>,
+    ) -> Option<String> {
+        let handle = self.selected.as_ref()?;
+        if let RecursiveDependencyLoadState::Failed(e) =
+            server.recursive_dependency_load_state(handle)
+        {
+            return Some(e.to_string());
+        }
+        if let LoadState::Failed(e) = server.load_state(handle) {
+            return Some(e.to_string());
+        }
+        let theme = themes.get(handle)?;
+        let size = images.get(&theme.atlas)?.size();
+        theme.check_atlas(size).err().map(|e| e.to_string())
+    }
+}
+
+pub fn load_theme_index(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    mut preload: ResMut<Preload>,
+) {
+    let index = server.load(THEME_INDEX_PATH);
+    preload.add(&index);
+    commands.insert_resource(Themes {
+        index,
+        selected: None,
+    });
+}
+
+/// Loads the theme picked in the settings, changes to its files are picked up while it is shown.
+pub fn select_theme(
+    mut themes: ResMut<Themes>,
+    settings: Res<CardFaceSettings>,
+    server: Res<AssetServer>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    themes.selected = settings
+        .theme
+        .as_deref()
+        .map(|directory| server.load(theme_path(directory)));
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::card::{Rank, Suit};
+
+    fn manifest(cards: Vec<serde_json::Value>) -> serde_json::Value {
+        json!({
+            "name": "Paper",
+            "atlas": "atlas.png",
+            "cards": cards,
+            "back": "back.png",
+            "felt": [20, 60, 30]
+        })
+    }
+
+    fn all_cards() -> Vec<serde_json::Value> {
+        (0..FACES as u8)
+            .map(|index| {
+                let card = Card::try_from(index).unwrap();
+                json!({"card": card, "rect": [index as u32 * 10, 0, 10, 14]})
+            })
+            .collect()
+    }
+
+    fn parse(value: serde_json::Value) -> Result<ThemeManifest, ThemeError> {
+        ThemeManifest::parse(&serde_json::to_vec(&value).unwrap())
+    }
+
+    #[test]
+    fn test_manifest_complete() {
+        let manifest = parse(manifest(all_cards())).unwrap();
+        assert_eq!("Paper", manifest.name);
+        assert_eq!(Some([20, 60, 30]), manifest.felt);
+        assert_eq!(FACES, manifest.cards.len());
+    }
+
+    #[test]
+    fn test_manifest_missing_cards() {
+        let mut cards = all_cards();
+        cards.truncate(FACES - 2);
+        match parse(manifest(cards)) {
+            Err(ThemeError::MissingCards(missing)) => assert_eq!(
+                vec![
+                    Card {
+                        suit: Suit::Spades,
+                        rank: Rank::King
+                    },
+                    Card {
+                        suit: Suit::Spades,
+                        rank: Rank::Ace
+                    },
+                ],
+                missing
+            ),
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_manifest_duplicate_card() {
+        let mut cards = all_cards();
+        cards[1] = cards[0].clone();
+        assert!(matches!(
+            parse(manifest(cards)),
+            Err(ThemeError::DuplicateCard(_))
+        ));
+    }
+
+    #[test]
+    fn test_manifest_rect_overflow() {
+        let mut cards = all_cards();
+        cards[3]["rect"] = json!([u32::MAX, 0, 10, 14]);
+        assert!(matches!(
+            parse(manifest(cards)),
+            Err(ThemeError::RectOverflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_theme_rects_outside_atlas() {
+        let manifest = parse(manifest(all_cards())).unwrap();
+        let theme = CardTheme {
+            atlas: default(),
+            cards: manifest
+                .cards
+                .iter()
+                .map(|card_rect| card_rect.urect().unwrap())
+                .collect(),
+            back: default(),
+            felt: None,
+        };
+        assert!(theme.check_atlas(UVec2::new(520, 14)).is_ok());
+        match theme.check_atlas(UVec2::new(510, 14)) {
+            Err(ThemeError::OutsideAtlas(cards, _)) => assert_eq!(
+                vec![Card {
+                    suit: Suit::Spades,
+                    rank: Rank::Ace
+                }],
+                cards
+            ),
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_manifest_empty_rect() {
+        let mut cards = all_cards();
+        cards[3]["rect"] = json!([0, 0, 0, 14]);
+        assert!(matches!(
+            parse(manifest(cards)),
+            Err(ThemeError::EmptyRect(_))
+        ));
+    }
+}
@@ -1,5 +1,9 @@
 use crate::assets::LoadingPlugin;
 use crate::connect::ConnectMenuPlugin;
+use crate::deep_link::DeepLinkPlugin;
+use crate::host::HostControlsPlugin;
+use crate::ingame::IngamePlugin;
+use crate::lobby::LobbyPlugin;
 use crate::login::LoginMenuPlugin;
 use crate::network::NetworkPlugin;
 use crate::rooms::RoomMenuPlugin;
@@ -9,8 +13,12 @@ use bevy::window::PrimaryWindow;
 use bevy_egui::EguiStartupSet;
 
 mod assets;
-mod card;
+pub mod card;
 mod connect;
+mod deep_link;
+mod host;
+mod ingame;
+mod lobby;
 mod login;
 mod network;
 mod rooms;
@@ -25,10 +33,15 @@ pub enum GameState {
     #[default]
     Starting,
     LoadingAssets,
+    /// An asset could not be loaded, the user may retry.
+    LoadingFailed,
     ConnectMenu,
     LoginMenu,
     RoomMenu,
+    Lobby,
     Ingame,
+    /// Steps through a recorded game, without a room on the server.
+    Replay,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
@@ -66,9 +79,13 @@ impl Plugin for GamePlugin {
                 BaseUiPlugin,
                 LoadingPlugin,
                 NetworkPlugin,
+                DeepLinkPlugin,
                 ConnectMenuPlugin,
                 LoginMenuPlugin,
                 RoomMenuPlugin,
+                LobbyPlugin,
+                IngamePlugin,
+                HostControlsPlugin,
             ));
     }
 }
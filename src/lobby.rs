@@ -0,0 +1,374 @@
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+use crate::card::VariantKind;
+use crate::deep_link::{invite_link, link_base};
+use crate::network::{
+    GameActionResult, GameInviteResult, GameLeaveResult, NetworkCommand, NetworkEvent, PlayerInfo,
+    RoomInfo, SeatRequest, WebSocketCommand, WebSocketResult,
+};
+use crate::{GameState, MySystemSets};
+
+pub struct LobbyPlugin;
+
+impl Plugin for LobbyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Lobby), add_ui_state)
+            .add_systems(
+                Update,
+                track_room_events
+                    .run_if(resource_exists::<CurrentRoom>)
+                    .before(MySystemSets::EguiTop),
+            )
+            .add_systems(
+                Update,
+                leave_room
+                    .run_if(resource_exists::<CurrentRoom>)
+                    .after(MySystemSets::Egui),
+            )
+            .add_systems(
+                Update,
+                (update_ui_state, lobby_menu)
+                    .chain()
+                    .run_if(in_state(GameState::Lobby))
+                    .in_set(MySystemSets::Egui),
+            )
+            .add_systems(OnExit(GameState::Lobby), remove_ui_state);
+    }
+}
+
+/// The room the user has joined, it lives from joining the room until leaving it.
+#[derive(Resource, Debug)]
+pub struct CurrentRoom {
+    pub room_id: String,
+    /// Our own user id, known after the server welcomed us on the websocket.
+    pub user_id: Option<String>,
+    /// Latest room snapshot from the server.
+    pub info: Option<RoomInfo>,
+}
+
+impl CurrentRoom {
+    pub fn new(room_id: impl Into<String>) -> Self {
+        Self {
+            room_id: room_id.into(),
+            user_id: None,
+            info: None,
+        }
+    }
+
+    pub fn me(&self) -> Option<&PlayerInfo> {
+        self.info.as_ref()?.player(self.user_id.as_ref()?)
+    }
+
+    pub fn is_host(&self) -> bool {
+        self.me().is_some_and(PlayerInfo::is_host)
+    }
+
+    /// Variant of the room, the default one until the server sent the room.
+    pub fn variant(&self) -> VariantKind {
+        self.info
+            .as_ref()
+            .map(|info| info.settings.variant)
+            .unwrap_or_default()
+    }
+
+    /// Spectators are known to the server but not listed as players.
+    pub fn is_spectator(&self) -> bool {
+        self.user_id.is_some() && self.info.is_some() && self.me().is_none()
+    }
+}
+
+fn track_room_events(mut room: ResMut<CurrentRoom>, mut network_events: EventReader<NetworkEvent>) {
+    for network_event in network_events.read() {
+        match network_event {
+            NetworkEvent::Welcome(welcome) => {
+                room.user_id = Some(welcome.user_id.to_owned());
+            }
+            NetworkEvent::RoomUpdate(info) => {
+                room.info = Some(info.to_owned());
+            }
+            NetworkEvent::GameStarted => {
+                if let Some(info) = room.info.as_mut() {
+                    info.started = true;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns to the room menu once the server confirmed that we left the room or were kicked.
+fn leave_room(
+    mut commands: Commands,
+    mut state: ResMut<NextState<GameState>>,
+    mut game_leave_results: EventReader<GameLeaveResult>,
+    mut network_events: EventReader<NetworkEvent>,
+    mut websocket_commands: EventWriter<WebSocketCommand>,
+) {
+    let left = matches!(
+        game_leave_results.read().last(),
+        Some(GameLeaveResult(Ok(_)))
+    );
+    let kicked = network_events
+        .read()
+        .any(|event| matches!(event, NetworkEvent::Kicked));
+    if left || kicked {
+        websocket_commands.send(WebSocketCommand::Close);
+        commands.remove_resource::<CurrentRoom>();
+        state.set(GameState::RoomMenu);
+    }
+}
+
+#[derive(Debug)]
+#[allow(dead_code)] // rust warns about an unread field, but we use it in the "Debug" impl
+enum LobbyStatus {
+    Connecting,
+    Connected,
+    Waiting,
+    Error(String),
+}
+
+#[derive(Resource)]
+struct UiState {
+    lobby_status: LobbyStatus,
+    invite_link: Option<String>,
+    /// Whether links to this client can be built, otherwise there is nothing to share.
+    can_invite: bool,
+}
+
+impl UiState {
+    fn interaction_blocked(&self) -> bool {
+        matches!(
+            self.lobby_status,
+            LobbyStatus::Connecting | LobbyStatus::Waiting
+        )
+    }
+
+    fn enable_label(&self) -> bool {
+        matches!(
+            self.lobby_status,
+            LobbyStatus::Connecting | LobbyStatus::Error(_)
+        )
+    }
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            lobby_status: LobbyStatus::Connecting,
+            invite_link: None,
+            can_invite: link_base().is_some(),
+        }
+    }
+}
+
+fn add_ui_state(
+    mut commands: Commands,
+    room: Res<CurrentRoom>,
+    mut event_writer: EventWriter<NetworkCommand>,
+) {
+    info!("starting Lobby");
+    commands.init_resource::<UiState>();
+    event_writer.send(NetworkCommand::GameSubscribe(room.room_id.to_owned()));
+}
+
+fn remove_ui_state(mut commands: Commands) {
+    commands.remove_resource::<UiState>();
+}
+
+fn update_ui_state(
+    mut state: ResMut<NextState<GameState>>,
+    mut ui_state: ResMut<UiState>,
+    room: Res<CurrentRoom>,
+    mut websocket_results: EventReader<WebSocketResult>,
+    mut game_action_results: EventReader<GameActionResult>,
+    mut game_leave_results: EventReader<GameLeaveResult>,
+    mut game_invite_results: EventReader<GameInviteResult>,
+) {
+    if let Some(websocket_result) = websocket_results.read().last() {
+        ui_state.lobby_status = match websocket_result {
+            WebSocketResult::Connected => LobbyStatus::Connected,
+            WebSocketResult::ConnectFailure => {
+                LobbyStatus::Error("Could not connect to room".to_string())
+            }
+            WebSocketResult::Disconnected => LobbyStatus::Error("Connection lost".to_string()),
+        };
+    }
+    if let Some(game_action_result) = game_action_results.read().last() {
+        ui_state.lobby_status = match &game_action_result.0 {
+            Ok(_) => LobbyStatus::Connected,
+            Err(e) => LobbyStatus::Error(format!("{e:?}")),
+        };
+    }
+    if let Some(GameLeaveResult(Err(e))) = game_leave_results.read().last() {
+        ui_state.lobby_status = LobbyStatus::Error(format!("{e:?}"));
+    }
+    if let Some(game_invite_result) = game_invite_results.read().last() {
+        match &game_invite_result.0 {
+            Ok(invite) => match invite_link(invite) {
+                Some(link) => {
+                    ui_state.lobby_status = LobbyStatus::Connected;
+                    ui_state.invite_link = Some(link.to_string());
+                }
+                None => {
+                    ui_state.lobby_status =
+                        LobbyStatus::Error("Could not build an invite link".to_string())
+                }
+            },
+            Err(e) => ui_state.lobby_status = LobbyStatus::Error(format!("{e:?}")),
+        }
+    }
+    if room.info.as_ref().is_some_and(|info| info.started) {
+        state.set(GameState::Ingame);
+    }
+}
+
+fn player_to_string(player: &PlayerInfo) -> String {
+    if player.is_host() {
+        format!("{} (host)", player.username)
+    } else {
+        player.username.to_owned()
+    }
+}
+
+fn lobby_menu(
+    mut egui_context: EguiContexts,
+    mut ui_state: ResMut<UiState>,
+    room: Res<CurrentRoom>,
+    mut event_writer: EventWriter<NetworkCommand>,
+) {
+    let ui_state: &mut UiState = &mut ui_state;
+    egui::CentralPanel::default().show(egui_context.ctx_mut(), |ui| {
+        let Some(info) = &room.info else {
+            ui.label(format!("Room: {}", room.room_id));
+            ui.spinner();
+            let button = ui.add_enabled(
+                !matches!(ui_state.lobby_status, LobbyStatus::Waiting),
+                egui::Button::new("Leave"),
+            );
+            if button.clicked() {
+                ui_state.lobby_status = LobbyStatus::Waiting;
+                event_writer.send(NetworkCommand::GameLeave(room.room_id.to_owned()));
+            }
+            ui.add_visible(
+                ui_state.enable_label(),
+                egui::Label::new(format!("{:?}", ui_state.lobby_status)),
+            );
+            return;
+        };
+        let me = room.me();
+        let ready = me.is_some_and(|me| me.ready);
+
+        ui.label(format!("Room: {}", info.name));
+        ui.label(format!("Variant: {}", info.settings.variant));
+        ui.separator();
+        egui::Grid::new("seats").striped(true).show(ui, |ui| {
+            for seat in 0..info.seats {
+                ui.label(format!("Seat {}", seat + 1));
+                match info.settings.variant.rules().team(seat) {
+                    Some(team) => ui.label(format!("Team {}", team + 1)),
+                    None => ui.label(""),
+                };
+                match info.seat_holder(seat) {
+                    Some(player) => {
+                        ui.label(player_to_string(player));
+                        ui.label(if player.ready { "ready" } else { "not ready" });
+                    }
+                    None => {
+                        let button = ui.add_enabled(
+                            !ui_state.interaction_blocked() && me.is_some() && !ready,
+                            egui::Button::new("Take seat"),
+                        );
+                        if button.clicked() {
+                            ui_state.lobby_status = LobbyStatus::Waiting;
+                            event_writer.send(NetworkCommand::GameSeat(
+                                room.room_id.to_owned(),
+                                SeatRequest { seat },
+                            ));
+                        }
+                        ui.label("");
+                    }
+                }
+                ui.end_row();
+            }
+        });
+        let unseated: Vec<String> = info
+            .players
+            .iter()
+            .filter(|p| p.seat.is_none())
+            .map(player_to_string)
+            .collect();
+        if !unseated.is_empty() {
+            ui.label(format!("Without seat: {}", unseated.join(", ")));
+        }
+        if info.spectators > 0 {
+            ui.label(format!("Spectators: {}", info.spectators));
+        }
+        if room.is_spectator() {
+            ui.label("You are watching as spectator.");
+        }
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            let mut checked = ready;
+            let checkbox = ui.add_enabled(
+                !ui_state.interaction_blocked() && me.is_some_and(|me| me.seat.is_some()),
+                egui::Checkbox::new(&mut checked, "Ready"),
+            );
+            if checkbox.changed() {
+                ui_state.lobby_status = LobbyStatus::Waiting;
+                event_writer.send(NetworkCommand::GameReady(room.room_id.to_owned(), checked));
+            }
+
+            if room.is_host() {
+                let button = ui.add_enabled(
+                    !ui_state.interaction_blocked() && info.can_start(),
+                    egui::Button::new("Start"),
+                );
+                if button.clicked() {
+                    ui_state.lobby_status = LobbyStatus::Waiting;
+                    event_writer.send(NetworkCommand::GameStart(room.room_id.to_owned()));
+                }
+            }
+
+            if ui_state.can_invite {
+                let button =
+                    ui.add_enabled(!ui_state.interaction_blocked(), egui::Button::new("Invite"));
+                if button.clicked() {
+                    ui_state.lobby_status = LobbyStatus::Waiting;
+                    event_writer.send(NetworkCommand::GameInvite(room.room_id.to_owned()));
+                }
+            }
+
+            let button = ui.add_enabled(
+                !matches!(ui_state.lobby_status, LobbyStatus::Waiting),
+                egui::Button::new("Leave"),
+            );
+            if button.clicked() {
+                ui_state.lobby_status = LobbyStatus::Waiting;
+                event_writer.send(NetworkCommand::GameLeave(room.room_id.to_owned()));
+            }
+        });
+        ui.add_visible(
+            ui_state.enable_label(),
+            egui::Label::new(format!("{:?}", ui_state.lobby_status)),
+        );
+    });
+
+    if let Some(link) = &ui_state.invite_link {
+        let mut close = false;
+        egui::Window::new("Invite").show(egui_context.ctx_mut(), |ui| {
+            ui.label("Share this link to let someone join the room:");
+            ui.add(egui::TextEdit::singleline(&mut link.as_str()));
+            ui.horizontal(|ui| {
+                if ui.button("Copy").clicked() {
+                    ui.ctx().copy_text(link.to_owned());
+                }
+                close = ui.button("Close").clicked();
+            });
+        });
+        if close {
+            ui_state.invite_link = None;
+        }
+    }
+}
@@ -62,7 +62,10 @@ impl<I: Debug, O: Debug> Worker<I, O> {
 
 impl<I: Debug, O: Debug> Worker<I, O> {
     pub fn send(&self, message: I) {
-        self.input.unbounded_send(message).unwrap();
+        if let Err(e) = self.input.unbounded_send(message) {
+            // the other side is gone, e.g. because its resource was removed
+            warn!("dropping message for closed worker: {:?}", e.into_inner());
+        }
     }
 
     pub fn try_recv(&mut self) -> Result<Option<O>, TryRecvError> {
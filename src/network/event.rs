@@ -1,5 +1,197 @@
+use std::fmt;
+
+use bevy::prelude::Event;
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub enum NetworkEvent {}
+use crate::card::{Card, CardVariant, HandScore, Suit, TeamValues};
+use crate::network::RoomInfo;
+
+/// Events pushed by the server over the websocket of a room.
+#[derive(Debug, Clone, Event, Serialize, Deserialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum NetworkEvent {
+    /// First event after subscribing, tells the client who it is.
+    Welcome(WelcomeEvent),
+    RoomUpdate(RoomInfo),
+    GameStarted,
+    /// The host removed us from the room.
+    Kicked,
+    HandDealt(HandDealtEvent),
+    Turn(TurnEvent),
+    CardPlayed(CardPlayedEvent),
+    TrickTaken(TrickTakenEvent),
+    /// A hand was finished and scored.
+    Score(ScoreEvent),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WelcomeEvent {
+    pub user_id: String,
+}
+
+/// A new hand was dealt, cards we may not see are sent as [`CardVariant::Back`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HandDealtEvent {
+    pub dealer: u8,
+    /// Hands indexed by seat.
+    pub hands: Vec<Vec<CardVariant>>,
+    #[serde(default)]
+    pub trump: Option<Suit>,
+    /// Card turned up to decide trumps, if the variant reveals one.
+    #[serde(default)]
+    pub turned_up: Option<Card>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TurnEvent {
+    pub seat: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CardPlayedEvent {
+    pub seat: u8,
+    pub card: Card,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TrickTakenEvent {
+    pub winner: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ScoreEvent {
+    pub hand: HandScore,
+    /// Points in the current game after this hand.
+    pub game_points: TeamValues<u32>,
+    pub games: TeamValues<u8>,
+}
+
+/// First message sent over the websocket of a room to authenticate the connection.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SubscribeRequest {
+    pub token: String,
+}
+
+impl SubscribeRequest {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+        }
+    }
+}
+
+impl fmt::Debug for SubscribeRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SubscribeRequest")
+            .field("token", &"****")
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::card::{Card, CardVariant, HandScore, Rank, Suit};
+    use crate::network::{CardPlayedEvent, HandDealtEvent, NetworkEvent, ScoreEvent, WelcomeEvent};
+
+    #[test]
+    fn test_network_event_deserialize_welcome() {
+        let actual: NetworkEvent =
+            serde_json::from_value(json!({"event": "welcome", "data": {"user_id": "abc"}}))
+                .unwrap();
+        match actual {
+            NetworkEvent::Welcome(welcome) => assert_eq!(
+                WelcomeEvent {
+                    user_id: "abc".to_string()
+                },
+                welcome
+            ),
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_network_event_deserialize_unit() {
+        let actual: NetworkEvent =
+            serde_json::from_value(json!({"event": "game_started"})).unwrap();
+        assert!(matches!(actual, NetworkEvent::GameStarted));
+    }
+
+    #[test]
+    fn test_network_event_deserialize_hand_dealt() {
+        let actual: NetworkEvent = serde_json::from_value(json!({
+            "event": "hand_dealt",
+            "data": {
+                "dealer": 3,
+                "hands": [[{"Front": {"suit": "hearts", "rank": "ace"}}], ["Back"]],
+                "trump": "clubs",
+                "turned_up": {"suit": "clubs", "rank": "4"}
+            }
+        }))
+        .unwrap();
+        let expected = HandDealtEvent {
+            dealer: 3,
+            hands: vec![
+                vec![CardVariant::Front(Card {
+                    suit: Suit::Hearts,
+                    rank: Rank::Ace,
+                })],
+                vec![CardVariant::Back],
+            ],
+            trump: Some(Suit::Clubs),
+            turned_up: Some(Card {
+                suit: Suit::Clubs,
+                rank: Rank::Num4,
+            }),
+        };
+        match actual {
+            NetworkEvent::HandDealt(hand_dealt) => assert_eq!(expected, hand_dealt),
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_network_event_deserialize_card_played() {
+        let actual: NetworkEvent = serde_json::from_value(json!({
+            "event": "card_played",
+            "data": {"seat": 1, "card": {"suit": "clubs", "rank": "10"}}
+        }))
+        .unwrap();
+        let expected = CardPlayedEvent {
+            seat: 1,
+            card: Card {
+                suit: Suit::Clubs,
+                rank: Rank::Num10,
+            },
+        };
+        match actual {
+            NetworkEvent::CardPlayed(card_played) => assert_eq!(expected, card_played),
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_network_event_deserialize_score() {
+        let actual: NetworkEvent = serde_json::from_value(json!({
+            "event": "score",
+            "data": {
+                "hand": {"tricks": [8, 5], "trick_points": [2, 0], "honour_points": [0, 4]},
+                "game_points": [2, 4],
+                "games": [1, 0]
+            }
+        }))
+        .unwrap();
+        let expected = ScoreEvent {
+            hand: HandScore::new([8, 5], [0, 4]),
+            game_points: [2, 4],
+            games: [1, 0],
+        };
+        match actual {
+            NetworkEvent::Score(score) => assert_eq!(expected, score),
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::card::Card;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GameListResponse {
     pub rooms: Vec<String>,
@@ -16,6 +18,10 @@ pub struct GameCreateRequest {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GameJoinRequest {
     pub password: Option<String>,
+    /// One-time token from an invite link.
+    pub invite: Option<String>,
+    /// Joins without a seat to watch the game.
+    pub spectator: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -45,12 +51,17 @@ pub struct GameCreateResponse {
     pub room_id: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PlayCardRequest {
+    pub card: Card,
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
     use serde_json::json;
 
-    use crate::network::{GameCreateRequest, GameJoinResponse, GameJoinStatus};
+    use crate::network::{GameCreateRequest, GameJoinRequest, GameJoinResponse, GameJoinStatus};
 
     #[test]
     fn test_game_join_response_deserialize_1() {
@@ -71,6 +82,22 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_game_join_request_serialize_spectator() {
+        let expected = json!({
+            "password": null,
+            "invite": null,
+            "spectator": true,
+        });
+        let actual = serde_json::to_value(GameJoinRequest {
+            password: None,
+            invite: None,
+            spectator: true,
+        })
+        .unwrap();
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     fn test_game_create_request_serialize_1() {
         let expected = json!({
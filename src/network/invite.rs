@@ -0,0 +1,136 @@
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+/// Scheme of invite links passed to the native client.
+pub const INVITE_SCHEME: &str = "whist";
+
+const SERVER_PARAM: &str = "server";
+const ROOM_PARAM: &str = "room";
+const TOKEN_PARAM: &str = "invite";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InviteError {
+    InvalidUrl(String),
+    MissingParameter(&'static str),
+}
+
+/// Everything needed to join a room straight from a link.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Invite {
+    pub server_url: String,
+    pub room_id: String,
+    /// One-time token, allows joining password protected rooms.
+    pub token: Option<String>,
+}
+
+impl Invite {
+    pub fn new(
+        server_url: impl Into<String>,
+        room_id: impl Into<String>,
+        token: Option<String>,
+    ) -> Self {
+        Self {
+            server_url: server_url.into(),
+            room_id: room_id.into(),
+            token,
+        }
+    }
+
+    /// Reads an invite from the query of a link.
+    ///
+    /// Returns `None` if the link does not look like an invite at all.
+    pub fn from_url(url: &Url) -> Option<Result<Self, InviteError>> {
+        let param = |name| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+        let room_id = param(ROOM_PARAM)?;
+        let Some(server_url) = param(SERVER_PARAM) else {
+            return Some(Err(InviteError::MissingParameter(SERVER_PARAM)));
+        };
+        if let Err(e) = Url::parse(&server_url) {
+            return Some(Err(InviteError::InvalidUrl(format!("{e}"))));
+        }
+        Some(Ok(Self::new(server_url, room_id, param(TOKEN_PARAM))))
+    }
+
+    /// Same as [`Invite::from_url`] but parses the link first.
+    pub fn from_link(link: impl AsRef<str>) -> Option<Result<Self, InviteError>> {
+        match Url::parse(link.as_ref()) {
+            Ok(url) => Self::from_url(&url),
+            Err(e) => Some(Err(InviteError::InvalidUrl(format!("{e}")))),
+        }
+    }
+
+    /// Builds a shareable link by appending the invite to `base`,
+    /// e.g. the page url in the browser or `whist://join` natively.
+    pub fn to_link(&self, base: &Url) -> Url {
+        let mut url = base.to_owned();
+        url.set_query(None);
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair(SERVER_PARAM, &self.server_url)
+                .append_pair(ROOM_PARAM, &self.room_id);
+            if let Some(token) = &self.token {
+                query.append_pair(TOKEN_PARAM, token);
+            }
+        }
+        url
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct InviteResponse {
+    pub token: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use reqwest::Url;
+
+    use crate::network::{Invite, InviteError};
+
+    #[test]
+    fn test_invite_from_native_link() {
+        let expected = Invite::new("http://localhost:8080/", "abc", Some("xyz".to_string()));
+        let actual = Invite::from_link(
+            "whist://join?server=http%3A%2F%2Flocalhost%3A8080%2F&room=abc&invite=xyz",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_invite_from_page_url_without_token() {
+        let expected = Invite::new("https://whist.example/", "abc", None);
+        let url =
+            Url::parse("https://play.example/app/?room=abc&server=https://whist.example/").unwrap();
+        assert_eq!(expected, Invite::from_url(&url).unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_invite_from_url_without_room() {
+        let url = Url::parse("https://play.example/?foo=bar").unwrap();
+        assert_eq!(None, Invite::from_url(&url));
+    }
+
+    #[test]
+    fn test_invite_from_url_without_server() {
+        let url = Url::parse("https://play.example/?room=abc").unwrap();
+        assert_eq!(
+            Some(Err(InviteError::MissingParameter("server"))),
+            Invite::from_url(&url)
+        );
+    }
+
+    #[test]
+    fn test_invite_round_trip() {
+        let invite = Invite::new("http://localhost:8080/", "abc", Some("x y".to_string()));
+        let link = invite.to_link(&Url::parse("whist://join?old=1").unwrap());
+        assert_eq!(invite, Invite::from_url(&link).unwrap().unwrap());
+    }
+}
@@ -6,7 +6,9 @@ pub use event::*;
 pub use game::*;
 pub use github::*;
 pub use github_service::*;
+pub use invite::*;
 pub use plugin::*;
+pub use room::*;
 pub use server_connection::*;
 pub use server_service::*;
 pub use user::*;
@@ -18,7 +20,9 @@ mod event;
 mod game;
 mod github;
 mod github_service;
+mod invite;
 mod plugin;
+mod room;
 mod server_connection;
 mod server_service;
 mod user;
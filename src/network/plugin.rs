@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use bevy::tasks::IoTaskPool;
+use futures::future::{self, Either};
 
 use crate::network::*;
 
@@ -14,9 +15,15 @@ impl Plugin for NetworkPlugin {
             .add_event::<GameJoinResult>()
             .add_event::<GameReconnectResult>()
             .add_event::<GameCreateResult>()
+            .add_event::<GameActionResult>()
+            .add_event::<GameLeaveResult>()
+            .add_event::<GamePlayResult>()
+            .add_event::<GameInviteResult>()
             .add_event::<GitHubTempTokenResult>()
             .add_event::<UserCreateResult>()
             .add_event::<WebSocketCommand>()
+            .add_event::<WebSocketResult>()
+            .add_event::<NetworkEvent>()
             .add_systems(Startup, setup_worker)
             .add_systems(
                 Update,
@@ -54,6 +61,17 @@ pub enum NetworkCommand {
     GameReconnect,
     GithubAuth(GitHubAuthRequest),
     SwapToken(SwapTokenRequest),
+    GameSubscribe(String),
+    GameSeat(String, SeatRequest),
+    GameReady(String, bool),
+    GameStart(String),
+    GameLeave(String),
+    GameInvite(String),
+    GamePlay(String, PlayCardRequest),
+    GameKick(String, PlayerRequest),
+    GameTransferHost(String, PlayerRequest),
+    GamePassword(String, PasswordRequest),
+    GameSettings(String, RoomSettings),
 }
 
 #[derive(Debug, Event)]
@@ -68,11 +86,25 @@ enum NetworkResponse {
     GameReconnect(GameReconnectResult),
     GameCreate(GameCreateResult),
     UserCreate(UserCreateResult),
+    GameSubscribe(String, SubscribeRequest),
+    GameAction(GameActionResult),
+    GameLeave(GameLeaveResult),
+    GameInvite(GameInviteResult),
+    GamePlay(GamePlayResult),
 }
 
 #[derive(Debug, Event)]
 pub enum WebSocketCommand {
-    Connect(String),
+    Connect(String, SubscribeRequest),
+    /// Closes the websocket of the current room, if any.
+    Close,
+}
+
+#[derive(Debug, Event)]
+pub enum WebSocketResult {
+    Connected,
+    ConnectFailure,
+    Disconnected,
 }
 
 #[derive(Debug)]
@@ -165,6 +197,84 @@ async fn network_worker(mut worker: NetworkWorkerFlipped) {
                         .await,
                 ));
             }
+            NetworkCommand::GameSubscribe(id) => {
+                let (url, request) = server_service.as_ref().unwrap().game_subscription(id);
+                worker.send(NetworkResponse::GameSubscribe(url.to_string(), request));
+            }
+            NetworkCommand::GameSeat(id, seat_request) => {
+                worker.send(NetworkResponse::GameAction(
+                    server_service
+                        .as_ref()
+                        .unwrap()
+                        .choose_seat(id, &seat_request)
+                        .await,
+                ));
+            }
+            NetworkCommand::GameReady(id, ready) => {
+                worker.send(NetworkResponse::GameAction(
+                    server_service.as_ref().unwrap().set_ready(id, ready).await,
+                ));
+            }
+            NetworkCommand::GameStart(id) => {
+                worker.send(NetworkResponse::GameAction(
+                    server_service.as_ref().unwrap().start_game(id).await,
+                ));
+            }
+            NetworkCommand::GameInvite(id) => {
+                worker.send(NetworkResponse::GameInvite(
+                    server_service.as_ref().unwrap().create_invite(id).await,
+                ));
+            }
+            NetworkCommand::GameKick(id, player_request) => {
+                worker.send(NetworkResponse::GameAction(
+                    server_service
+                        .as_ref()
+                        .unwrap()
+                        .kick_player(id, &player_request)
+                        .await,
+                ));
+            }
+            NetworkCommand::GameTransferHost(id, player_request) => {
+                worker.send(NetworkResponse::GameAction(
+                    server_service
+                        .as_ref()
+                        .unwrap()
+                        .transfer_host(id, &player_request)
+                        .await,
+                ));
+            }
+            NetworkCommand::GamePassword(id, password_request) => {
+                worker.send(NetworkResponse::GameAction(
+                    server_service
+                        .as_ref()
+                        .unwrap()
+                        .change_password(id, &password_request)
+                        .await,
+                ));
+            }
+            NetworkCommand::GameSettings(id, room_settings) => {
+                worker.send(NetworkResponse::GameAction(
+                    server_service
+                        .as_ref()
+                        .unwrap()
+                        .update_settings(id, &room_settings)
+                        .await,
+                ));
+            }
+            NetworkCommand::GamePlay(id, play_card_request) => {
+                worker.send(NetworkResponse::GamePlay(
+                    server_service
+                        .as_ref()
+                        .unwrap()
+                        .play_card(id, &play_card_request)
+                        .await,
+                ));
+            }
+            NetworkCommand::GameLeave(id) => {
+                worker.send(NetworkResponse::GameLeave(
+                    server_service.as_ref().unwrap().leave_game(id).await,
+                ));
+            }
         }
     }
 }
@@ -191,6 +301,11 @@ fn receive_network_events(
     mut game_join_result: EventWriter<GameJoinResult>,
     mut game_reconnect_result: EventWriter<GameReconnectResult>,
     mut game_create_result: EventWriter<GameCreateResult>,
+    mut game_action_result: EventWriter<GameActionResult>,
+    mut game_leave_result: EventWriter<GameLeaveResult>,
+    mut game_invite_result: EventWriter<GameInviteResult>,
+    mut game_play_result: EventWriter<GamePlayResult>,
+    mut websocket_commands: EventWriter<WebSocketCommand>,
 ) {
     if let Some(mut network_worker) = network_worker {
         while let Ok(Some(network_response)) = network_worker.try_recv() {
@@ -226,6 +341,21 @@ fn receive_network_events(
                 NetworkResponse::GameCreate(result) => {
                     game_create_result.send(result);
                 }
+                NetworkResponse::GameSubscribe(url, request) => {
+                    websocket_commands.send(WebSocketCommand::Connect(url, request));
+                }
+                NetworkResponse::GameAction(result) => {
+                    game_action_result.send(result);
+                }
+                NetworkResponse::GameLeave(result) => {
+                    game_leave_result.send(result);
+                }
+                NetworkResponse::GameInvite(result) => {
+                    game_invite_result.send(result);
+                }
+                NetworkResponse::GamePlay(result) => {
+                    game_play_result.send(result);
+                }
             }
         }
     }
@@ -238,20 +368,44 @@ fn send_websocket_commands(
     for websocket_event in websocket_events.read() {
         info!("websocket event: {:?}", websocket_event);
         match websocket_event {
-            WebSocketCommand::Connect(url) => {
+            WebSocketCommand::Connect(url, request) => {
                 let url = url.to_owned();
+                let request = request.to_owned();
                 let worker = WebSocketWorker::spawn(
                     IoTaskPool::get(),
-                    |worker: WebSocketWorkerFlipped| async move {
+                    |mut worker: WebSocketWorkerFlipped| async move {
                         match WebSocket::connect(url).await {
-                            Ok((_sender, mut receiver)) => {
+                            Ok((mut sender, mut receiver)) => {
+                                if sender.send_json(&request).await.is_err() {
+                                    worker.send(WebSocketResponse::ConnectError);
+                                    return;
+                                }
                                 worker.send(WebSocketResponse::ConnectSuccess);
-                                while let Result::<NetworkEvent, _>::Ok(event) =
-                                    receiver.recv_json().await
-                                {
-                                    worker.send(WebSocketResponse::Event(event));
+                                loop {
+                                    // the worker channel closes once the resource is removed
+                                    let next = match future::select(
+                                        Box::pin(receiver.recv_json::<NetworkEvent>()),
+                                        Box::pin(worker.recv()),
+                                    )
+                                    .await
+                                    {
+                                        Either::Left((event, _)) => Some(event),
+                                        Either::Right(_) => None,
+                                    };
+                                    match next {
+                                        Some(Ok(event)) => {
+                                            worker.send(WebSocketResponse::Event(event));
+                                        }
+                                        Some(Err(_)) => {
+                                            worker.send(WebSocketResponse::Error);
+                                            break;
+                                        }
+                                        None => {
+                                            let _ = sender.close().await;
+                                            break;
+                                        }
+                                    }
                                 }
-                                worker.send(WebSocketResponse::Error);
                             }
                             Err(_) => {
                                 worker.send(WebSocketResponse::ConnectError);
@@ -261,19 +415,34 @@ fn send_websocket_commands(
                 );
                 commands.insert_resource(worker);
             }
+            WebSocketCommand::Close => {
+                commands.remove_resource::<WebSocketWorker>();
+            }
         }
     }
 }
 
-fn receive_websocket_responses(websocket_worker: Option<ResMut<WebSocketWorker>>) {
+fn receive_websocket_responses(
+    websocket_worker: Option<ResMut<WebSocketWorker>>,
+    mut websocket_result: EventWriter<WebSocketResult>,
+    mut network_events: EventWriter<NetworkEvent>,
+) {
     if let Some(mut websocket_worker) = websocket_worker {
         while let Ok(Some(websocket_response)) = websocket_worker.try_recv() {
             info!("websocket response: {:?}", websocket_response);
             match websocket_response {
-                WebSocketResponse::ConnectSuccess => {}
-                WebSocketResponse::ConnectError => panic!("websocket connect error"),
-                WebSocketResponse::Event(event) => todo!("handle {:?} event", event),
-                WebSocketResponse::Error => panic!("websocket error"),
+                WebSocketResponse::ConnectSuccess => {
+                    websocket_result.send(WebSocketResult::Connected);
+                }
+                WebSocketResponse::ConnectError => {
+                    websocket_result.send(WebSocketResult::ConnectFailure);
+                }
+                WebSocketResponse::Event(event) => {
+                    network_events.send(event);
+                }
+                WebSocketResponse::Error => {
+                    websocket_result.send(WebSocketResult::Disconnected);
+                }
             }
         }
     }
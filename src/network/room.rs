@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+
+use crate::card::{VariantKind, team_of_seat};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerRole {
+    Host,
+    Player,
+}
+
+/// A user inside a room as reported by the server.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PlayerInfo {
+    pub user_id: String,
+    pub username: String,
+    pub role: PlayerRole,
+    pub seat: Option<u8>,
+    pub ready: bool,
+}
+
+impl PlayerInfo {
+    pub fn is_host(&self) -> bool {
+        self.role == PlayerRole::Host
+    }
+
+    pub fn team(&self) -> Option<u8> {
+        self.seat.map(team_of_seat)
+    }
+}
+
+/// How far back players may look at the tricks of the current hand.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrickHistory {
+    #[default]
+    Full,
+    LastTrick,
+}
+
+/// Settings of a room the host can change.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoomSettings {
+    pub min_player: u8,
+    pub max_player: u8,
+    pub allow_spectators: bool,
+    pub variant: VariantKind,
+    /// Scores honours, the ace, king, queen and jack of trumps.
+    pub honours: bool,
+    pub trick_history: TrickHistory,
+}
+
+impl Default for RoomSettings {
+    fn default() -> Self {
+        Self {
+            min_player: 4,
+            max_player: 4,
+            allow_spectators: true,
+            variant: VariantKind::Classic,
+            honours: false,
+            trick_history: TrickHistory::Full,
+        }
+    }
+}
+
+/// Snapshot of a room, sent by the server whenever something in the lobby changes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RoomInfo {
+    pub room_id: String,
+    pub name: String,
+    /// Number of seats at the table.
+    pub seats: u8,
+    pub started: bool,
+    pub players: Vec<PlayerInfo>,
+    #[serde(default)]
+    pub settings: RoomSettings,
+    /// Number of users watching the room without a seat.
+    #[serde(default)]
+    pub spectators: u32,
+}
+
+impl RoomInfo {
+    pub fn player(&self, user_id: impl AsRef<str>) -> Option<&PlayerInfo> {
+        self.players.iter().find(|p| p.user_id == user_id.as_ref())
+    }
+
+    pub fn seat_holder(&self, seat: u8) -> Option<&PlayerInfo> {
+        self.players.iter().find(|p| p.seat == Some(seat))
+    }
+
+    pub fn seats_filled(&self) -> bool {
+        (0..self.seats).all(|seat| self.seat_holder(seat).is_some())
+    }
+
+    /// The host may start the game once every seat is taken and all seated players are ready.
+    pub fn can_start(&self) -> bool {
+        !self.started
+            && self.seats_filled()
+            && self
+                .players
+                .iter()
+                .filter(|p| p.seat.is_some())
+                .all(|p| p.ready)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SeatRequest {
+    pub seat: u8,
+}
+
+/// Names the player a host action is targeted at.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PlayerRequest {
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PasswordRequest {
+    pub password: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::card::VariantKind;
+    use crate::network::{PlayerInfo, PlayerRole, RoomInfo, RoomSettings, TrickHistory};
+
+    fn player(user_id: &str, seat: Option<u8>, ready: bool) -> PlayerInfo {
+        PlayerInfo {
+            user_id: user_id.to_string(),
+            username: user_id.to_string(),
+            role: PlayerRole::Player,
+            seat,
+            ready,
+        }
+    }
+
+    fn room(players: Vec<PlayerInfo>) -> RoomInfo {
+        RoomInfo {
+            room_id: "1".to_string(),
+            name: "table".to_string(),
+            seats: 4,
+            started: false,
+            players,
+            settings: RoomSettings::default(),
+            spectators: 0,
+        }
+    }
+
+    #[test]
+    fn test_room_info_deserialize() {
+        let expected = room(vec![PlayerInfo {
+            role: PlayerRole::Host,
+            ..player("a", Some(2), true)
+        }]);
+        let actual: RoomInfo = serde_json::from_value(json!({
+            "room_id": "1",
+            "name": "table",
+            "seats": 4,
+            "started": false,
+            "players": [
+                {"user_id": "a", "username": "a", "role": "host", "seat": 2, "ready": true}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_room_info_deserialize_settings() {
+        let actual: RoomInfo = serde_json::from_value(json!({
+            "room_id": "1",
+            "name": "table",
+            "seats": 4,
+            "started": false,
+            "players": [],
+            "settings": {"min_player": 2, "max_player": 6, "variant": "oh_hell"}
+        }))
+        .unwrap();
+        assert_eq!(
+            RoomSettings {
+                min_player: 2,
+                max_player: 6,
+                allow_spectators: true,
+                variant: VariantKind::OhHell,
+                honours: false,
+                trick_history: TrickHistory::Full,
+            },
+            actual.settings
+        );
+    }
+
+    #[test]
+    fn test_room_info_deserialize_spectators() {
+        let actual: RoomInfo = serde_json::from_value(json!({
+            "room_id": "1",
+            "name": "table",
+            "seats": 4,
+            "started": true,
+            "players": [],
+            "settings": {
+                "min_player": 4,
+                "max_player": 4,
+                "allow_spectators": false,
+                "trick_history": "last_trick"
+            },
+            "spectators": 3
+        }))
+        .unwrap();
+        assert!(!actual.settings.allow_spectators);
+        assert_eq!(TrickHistory::LastTrick, actual.settings.trick_history);
+        assert_eq!(3, actual.spectators);
+    }
+
+    #[test]
+    fn test_can_start_with_empty_seat() {
+        let room = room(vec![
+            player("a", Some(0), true),
+            player("b", Some(1), true),
+            player("c", Some(2), true),
+            player("d", None, true),
+        ]);
+        assert!(!room.seats_filled());
+        assert!(!room.can_start());
+    }
+
+    #[test]
+    fn test_can_start_not_ready() {
+        let room = room(vec![
+            player("a", Some(0), true),
+            player("b", Some(1), false),
+            player("c", Some(2), true),
+            player("d", Some(3), true),
+        ]);
+        assert!(room.seats_filled());
+        assert!(!room.can_start());
+    }
+
+    #[test]
+    fn test_can_start() {
+        let room = room(vec![
+            player("a", Some(0), true),
+            player("b", Some(1), true),
+            player("c", Some(2), true),
+            player("d", Some(3), true),
+            player("e", None, false),
+        ]);
+        assert!(room.can_start());
+    }
+}
@@ -72,6 +72,10 @@ impl ServerConnection {
         self.token = None;
     }
 
+    pub fn access_token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
     /// Does a HTTP request and returns the raw response.
     ///
     /// # Arguments
@@ -160,7 +164,7 @@ impl ServerConnection {
             .await
     }
 
-    fn join_url(&self, route: impl AsRef<str>) -> Url {
+    pub fn join_url(&self, route: impl AsRef<str>) -> Url {
         let route_ref = route.as_ref();
         assert!(!route_ref.starts_with('/'), "route must be relative");
         self.base_url.join(route_ref).unwrap()
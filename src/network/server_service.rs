@@ -1,5 +1,8 @@
+use std::fmt::Debug;
+
 use bevy::ecs::event::*;
-use reqwest::{Error, IntoUrl, Method};
+use reqwest::{Error, IntoUrl, Method, Url};
+use serde::Serialize;
 
 use crate::network::*;
 
@@ -43,6 +46,20 @@ pub struct GameReconnectResult(pub Result<GameReconnectResponse, Error>);
 #[derive(Debug, Event)]
 pub struct GameCreateResult(pub Result<GameCreateResponse, Error>);
 
+/// Result of an action inside a room that has no response body, e.g. taking a seat.
+#[derive(Debug, Event)]
+pub struct GameActionResult(pub Result<(), Error>);
+
+#[derive(Debug, Event)]
+pub struct GameLeaveResult(pub Result<(), Error>);
+
+/// Result of playing a card, kept apart from [`GameActionResult`] so rejected plays can be undone.
+#[derive(Debug, Event)]
+pub struct GamePlayResult(pub Result<(), Error>);
+
+#[derive(Debug, Event)]
+pub struct GameInviteResult(pub Result<Invite, Error>);
+
 impl ServerService {
     /// Constructor
     /// # Arguments
@@ -180,14 +197,159 @@ impl ServerService {
                 .await,
         )
     }
+
+    /// Requests a one-time invite token for a room.
+    pub async fn create_invite(&self, game_id: impl AsRef<str>) -> GameInviteResult {
+        let res: Result<InviteResponse, Error> = self
+            .server_connection
+            .request_with_json_result(
+                Method::POST,
+                format!("room/invite/{}", game_id.as_ref()),
+                Query::<()>::None,
+                Body::<()>::Empty,
+                None,
+            )
+            .await;
+        GameInviteResult(res.map(|res| {
+            Invite::new(
+                self.server_connection.base_url().as_str(),
+                game_id.as_ref(),
+                Some(res.token),
+            )
+        }))
+    }
+
+    pub async fn leave_game(&self, game_id: impl AsRef<str>) -> GameLeaveResult {
+        GameLeaveResult(
+            self.server_connection
+                .request(
+                    Method::POST,
+                    format!("room/leave/{}", game_id.as_ref()),
+                    Query::<()>::None,
+                    Body::<()>::Empty,
+                    None,
+                )
+                .await
+                .map(|_| ()),
+        )
+    }
+
+    /// Returns the websocket url of a room together with the message authenticating it.
+    pub fn game_subscription(&self, game_id: impl AsRef<str>) -> (Url, SubscribeRequest) {
+        let url = self
+            .server_connection
+            .join_url(format!("room/ws/{}", game_id.as_ref()));
+        let token = self.server_connection.access_token().unwrap_or_default();
+        (url, SubscribeRequest::new(token))
+    }
+
+    pub async fn choose_seat(
+        &self,
+        game_id: impl AsRef<str>,
+        body: &SeatRequest,
+    ) -> GameActionResult {
+        self.game_action(format!("room/seat/{}", game_id.as_ref()), Body::Json(body))
+            .await
+    }
+
+    pub async fn set_ready(&self, game_id: impl AsRef<str>, ready: bool) -> GameActionResult {
+        let action = if ready { "ready" } else { "unready" };
+        self.game_action(
+            format!("room/action/{action}/{}", game_id.as_ref()),
+            Body::<()>::Empty,
+        )
+        .await
+    }
+
+    pub async fn start_game(&self, game_id: impl AsRef<str>) -> GameActionResult {
+        self.game_action(
+            format!("room/action/start/{}", game_id.as_ref()),
+            Body::<()>::Empty,
+        )
+        .await
+    }
+
+    pub async fn play_card(
+        &self,
+        game_id: impl AsRef<str>,
+        body: &PlayCardRequest,
+    ) -> GamePlayResult {
+        let result = self
+            .game_action(format!("room/play/{}", game_id.as_ref()), Body::Json(body))
+            .await;
+        GamePlayResult(result.0)
+    }
+
+    pub async fn kick_player(
+        &self,
+        game_id: impl AsRef<str>,
+        body: &PlayerRequest,
+    ) -> GameActionResult {
+        self.game_action(
+            format!("room/host/kick/{}", game_id.as_ref()),
+            Body::Json(body),
+        )
+        .await
+    }
+
+    pub async fn transfer_host(
+        &self,
+        game_id: impl AsRef<str>,
+        body: &PlayerRequest,
+    ) -> GameActionResult {
+        self.game_action(
+            format!("room/host/transfer/{}", game_id.as_ref()),
+            Body::Json(body),
+        )
+        .await
+    }
+
+    pub async fn change_password(
+        &self,
+        game_id: impl AsRef<str>,
+        body: &PasswordRequest,
+    ) -> GameActionResult {
+        self.game_action(
+            format!("room/host/password/{}", game_id.as_ref()),
+            Body::Json(body),
+        )
+        .await
+    }
+
+    pub async fn update_settings(
+        &self,
+        game_id: impl AsRef<str>,
+        body: &RoomSettings,
+    ) -> GameActionResult {
+        self.game_action(
+            format!("room/host/settings/{}", game_id.as_ref()),
+            Body::Json(body),
+        )
+        .await
+    }
+
+    async fn game_action<B: Serialize + Debug>(
+        &self,
+        route: impl AsRef<str>,
+        body: Body<'_, B>,
+    ) -> GameActionResult {
+        GameActionResult(
+            self.server_connection
+                .request(Method::POST, route, Query::<()>::None, body, None)
+                .await
+                .map(|_| ()),
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
-    use wiremock::matchers::method;
+    use serde_json::json;
+    use wiremock::matchers::{body_json, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
+    use crate::card::{Card, Rank, Suit, VariantKind};
     use crate::network::*;
 
     #[tokio::test]
@@ -203,4 +365,142 @@ mod tests {
         let response_json = service.get_info().await.unwrap();
         assert_eq!(response_json, expected_info);
     }
+
+    #[tokio::test]
+    async fn test_choose_seat() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/room/seat/abc"))
+            .and(body_json(json!({"seat": 2})))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let service = ServerService::new(mock_server.uri());
+        let result = service.choose_seat("abc", &SeatRequest { seat: 2 }).await;
+        assert!(result.0.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_start_game_rejected() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/room/action/start/abc"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&mock_server)
+            .await;
+        let service = ServerService::new(mock_server.uri());
+        let result = service.start_game("abc").await;
+        assert!(result.0.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_play_card_rejected() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/room/play/abc"))
+            .and(body_json(json!({"card": {"suit": "hearts", "rank": "10"}})))
+            .respond_with(ResponseTemplate::new(409))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let service = ServerService::new(mock_server.uri());
+        let body = PlayCardRequest {
+            card: Card {
+                suit: Suit::Hearts,
+                rank: Rank::Num10,
+            },
+        };
+        let result = service.play_card("abc", &body).await;
+        assert!(result.0.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_kick_player() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/room/host/kick/abc"))
+            .and(body_json(json!({"user_id": "def"})))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let service = ServerService::new(mock_server.uri());
+        let body = PlayerRequest {
+            user_id: "def".to_string(),
+        };
+        let result = service.kick_player("abc", &body).await;
+        assert!(result.0.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_update_settings() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/room/host/settings/abc"))
+            .and(body_json(json!({
+                "min_player": 4,
+                "max_player": 6,
+                "allow_spectators": false,
+                "variant": "solo",
+                "honours": true,
+                "trick_history": "last_trick"
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let service = ServerService::new(mock_server.uri());
+        let body = RoomSettings {
+            min_player: 4,
+            max_player: 6,
+            allow_spectators: false,
+            variant: VariantKind::Solo,
+            honours: true,
+            trick_history: TrickHistory::LastTrick,
+        };
+        let result = service.update_settings("abc", &body).await;
+        assert!(result.0.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_invite() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/room/invite/abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"token": "xyz"})))
+            .mount(&mock_server)
+            .await;
+        let service = ServerService::new(mock_server.uri());
+        let invite = service.create_invite("abc").await.0.unwrap();
+        assert_eq!(
+            Invite::new(
+                format!("{}/", mock_server.uri()),
+                "abc",
+                Some("xyz".to_string())
+            ),
+            invite
+        );
+    }
+
+    #[tokio::test]
+    async fn test_leave_game() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/room/leave/abc"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let service = ServerService::new(mock_server.uri());
+        let result = service.leave_game("abc").await;
+        assert!(result.0.is_ok());
+    }
+
+    #[test]
+    fn test_game_subscription() {
+        let service = ServerService::new("http://localhost:8080");
+        let (url, _) = service.game_subscription("abc");
+        assert_eq!("http://localhost:8080/room/ws/abc", url.as_str());
+    }
 }
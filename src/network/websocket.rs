@@ -102,6 +102,11 @@ impl WebSocketSender {
     ) -> Result<(), WebSocketError> {
         self.send_text(serde_json::to_string(data)?).await
     }
+
+    pub async fn close(&mut self) -> Result<(), WebSocketError> {
+        self.sink.close().await?;
+        Ok(())
+    }
 }
 
 #[cfg(target_family = "wasm")]
@@ -156,11 +161,14 @@ impl WebSocketReceiver {
 #[cfg(not(target_family = "wasm"))]
 impl WebSocketReceiver {
     pub async fn recv_text(&mut self) -> Result<String, WebSocketError> {
-        match self.stream.try_next().await? {
-            Some(tokio_tungstenite::tungstenite::protocol::Message::Text(data)) => {
-                Ok(data.to_string())
+        use tokio_tungstenite::tungstenite::protocol::Message;
+        loop {
+            match self.stream.try_next().await? {
+                Some(Message::Text(data)) => return Ok(data.to_string()),
+                // control frames are answered by tungstenite itself
+                Some(Message::Ping(_) | Message::Pong(_)) => {}
+                _ => return Err(WebSocketError::UnexpectedMessageType),
             }
-            _ => Err(WebSocketError::UnexpectedMessageType),
         }
     }
 }
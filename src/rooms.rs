@@ -3,6 +3,8 @@ use bevy_egui::egui::Ui;
 use bevy_egui::egui::scroll_area::ScrollBarVisibility;
 use bevy_egui::{EguiContexts, egui};
 
+use crate::deep_link::PendingInvite;
+use crate::lobby::CurrentRoom;
 use crate::network::{
     GameCreateRequest, GameCreateResult, GameJoinRequest, GameJoinResult, GameJoinStatus,
     GameListResult, GameReconnectResult, NetworkCommand,
@@ -44,6 +46,7 @@ struct UiState {
     selected: Option<String>,
     name: String,
     password: String,
+    spectator: bool,
 }
 
 impl UiState {
@@ -74,6 +77,10 @@ impl UiState {
         !self.main_interaction_blocked()
     }
 
+    fn enable_replay_button(&self) -> bool {
+        !self.main_interaction_blocked()
+    }
+
     fn enable_label(&self) -> bool {
         matches!(self.room_status, RoomStatus::Loading | RoomStatus::Error(_))
     }
@@ -95,6 +102,7 @@ impl UiState {
         self.room_status = RoomStatus::Loaded;
         self.name.clear();
         self.password.clear();
+        self.spectator = false;
     }
 }
 
@@ -106,6 +114,7 @@ impl Default for UiState {
             selected: None,
             name: "".to_string(),
             password: "".to_string(),
+            spectator: false,
         }
     }
 }
@@ -123,19 +132,26 @@ fn remove_ui_state(mut commands: Commands) {
     commands.remove_resource::<UiState>();
 }
 
+#[allow(clippy::too_many_arguments)]
 fn update_ui_state(
+    mut commands: Commands,
     mut state: ResMut<NextState<GameState>>,
     mut ui_state: ResMut<UiState>,
     mut game_list_results: EventReader<GameListResult>,
     mut game_join_results: EventReader<GameJoinResult>,
     mut game_reconnect_results: EventReader<GameReconnectResult>,
     mut game_create_results: EventReader<GameCreateResult>,
+    invite: Option<Res<PendingInvite>>,
     mut event_writer: EventWriter<NetworkCommand>,
 ) {
     if matches!(ui_state.room_status, RoomStatus::Loading) {
         event_writer.send(NetworkCommand::GameReconnect);
     }
     if let Some(game_reconnect_result) = game_reconnect_results.read().last() {
+        // the invite is only for this reconnect, it must not join a room later on
+        if invite.is_some() {
+            commands.remove_resource::<PendingInvite>();
+        }
         match &game_reconnect_result.0 {
             Ok(res) => match res.status {
                 GameJoinStatus::Joined | GameJoinStatus::AlreadyJoined => match res.password {
@@ -143,11 +159,35 @@ fn update_ui_state(
                         ui_state.selected.clone_from(&res.room_id);
                         ui_state.room_status = RoomStatus::JoinWindow
                     }
-                    _ => state.set(GameState::Ingame),
+                    _ => match &res.room_id {
+                        Some(room_id) => {
+                            commands.insert_resource(CurrentRoom::new(room_id.to_owned()));
+                            state.set(GameState::Lobby);
+                        }
+                        None => {
+                            ui_state.room_status =
+                                RoomStatus::Error("Reconnected without a room id".to_string());
+                        }
+                    },
+                },
+                GameJoinStatus::NotJoined => match invite {
+                    Some(invite) => {
+                        let invite = &invite.0;
+                        ui_state.selected = Some(invite.room_id.to_owned());
+                        ui_state.room_status = RoomStatus::Joining;
+                        event_writer.send(NetworkCommand::GameJoin(
+                            invite.room_id.to_owned(),
+                            GameJoinRequest {
+                                password: None,
+                                invite: invite.token.to_owned(),
+                                spectator: false,
+                            },
+                        ));
+                    }
+                    None => {
+                        event_writer.send(NetworkCommand::GetGameList);
+                    }
                 },
-                GameJoinStatus::NotJoined => {
-                    event_writer.send(NetworkCommand::GetGameList);
-                }
             },
             Err(e) => {
                 ui_state.room_status = RoomStatus::Error(format!("{e:?}"));
@@ -170,7 +210,16 @@ fn update_ui_state(
         match &game_join_result.0 {
             Ok(res) => match res.status {
                 GameJoinStatus::Joined | GameJoinStatus::AlreadyJoined => {
-                    state.set(GameState::Ingame);
+                    match ui_state.selected.clone() {
+                        Some(room_id) => {
+                            commands.insert_resource(CurrentRoom::new(room_id));
+                            state.set(GameState::Lobby);
+                        }
+                        None => {
+                            ui_state.room_status =
+                                RoomStatus::Error("Joined without a selected room".to_string());
+                        }
+                    }
                 }
                 GameJoinStatus::NotJoined => {
                     ui_state.room_status = RoomStatus::Error("Not joined".to_string())
@@ -187,8 +236,9 @@ fn update_ui_state(
             RoomStatus::CreatingAndJoining
         ));
         match &game_create_result.0 {
-            Ok(_) => {
-                state.set(GameState::Ingame);
+            Ok(res) => {
+                commands.insert_resource(CurrentRoom::new(&res.room_id));
+                state.set(GameState::Lobby);
             }
             Err(e) => {
                 ui_state.room_status = RoomStatus::Error(format!("{e:?}"));
@@ -199,6 +249,7 @@ fn update_ui_state(
 
 fn room_menu(
     mut egui_context: EguiContexts,
+    mut state: ResMut<NextState<GameState>>,
     mut ui_state: ResMut<UiState>,
     mut event_writer: EventWriter<NetworkCommand>,
 ) {
@@ -256,6 +307,13 @@ fn room_menu(
                 ui_state.reset();
                 ui_state.room_status = RoomStatus::CreateWindow;
             }
+
+            let button = ui
+                .add_enabled(ui_state.enable_replay_button(), egui::Button::new("Replay"))
+                .on_hover_text("Step through a game exported as JSON");
+            if button.clicked() {
+                state.set(GameState::Replay);
+            }
         });
         ui.add_visible(
             ui_state.enable_label(),
@@ -270,6 +328,7 @@ fn room_menu(
                     ui.label("Password:");
                     ui.text_edit_singleline(&mut ui_state.password);
                 });
+                ui.checkbox(&mut ui_state.spectator, "Watch as spectator");
                 ui.horizontal(|ui| {
                     let button = ui.add_enabled(
                         ui_state.enable_join_create_button(),
@@ -285,6 +344,8 @@ fn room_menu(
                                 } else {
                                     Some(ui_state.password.to_string())
                                 },
+                                invite: None,
+                                spectator: ui_state.spectator,
                             },
                         ));
                     }